@@ -1,17 +1,22 @@
 use std::env;
 
+/// Build-time settings forwarded to the app, read from the environment or `.env`.
+const KEYS: [&str; 2] = ["BACKEND_URL", "LIVE_UPDATES"];
+
 fn main() {
-    if let Ok(val) = env::var("BACKEND_URL") {
-        println!("cargo:rustc-env=BACKEND_URL={}", val);
-        return;
-    }
+    let from_file: Vec<(String, String)> = dotenvy::from_path_iter(".env")
+        .map(|iter| iter.flatten().collect())
+        .unwrap_or_default();
 
-    if let Ok(iter) = dotenvy::from_path_iter(".env") {
-        for entry in iter.flatten() {
-            if entry.0 == "BACKEND_URL" {
-                println!("cargo:rustc-env=BACKEND_URL={}", entry.1);
-                break;
-            }
+    for key in KEYS {
+        let value = env::var(key).ok().or_else(|| {
+            from_file
+                .iter()
+                .find(|entry| entry.0 == key)
+                .map(|entry| entry.1.clone())
+        });
+        if let Some(value) = value {
+            println!("cargo:rustc-env={}={}", key, value);
         }
     }
 }
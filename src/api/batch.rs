@@ -0,0 +1,202 @@
+use chrono::NaiveDate;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+use crate::api::client::TodoApiClient;
+use crate::api::error::ApiError;
+use crate::api::models::{Priority, Todo, TodoResponse, UpdateTodoType};
+use crate::types::Status;
+
+/// Body of `POST api/v1/todo/batch`: every update and delete in one request.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct TodoBatch {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub update: Vec<UpdateTodoType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delete: Vec<i64>,
+}
+
+impl TodoBatch {
+    pub fn len(&self) -> usize {
+        self.update.len() + self.delete.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// One change applied to every selected todo.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BulkAction {
+    Complete,
+    Reopen,
+    Delete,
+    /// `None` takes the todos out of every list.
+    MoveToList(Option<i64>),
+    AddTag(String),
+    SetPriority(Priority),
+}
+
+impl BulkAction {
+    /// Past tense used in the result report, e.g. "Completed 3 todos."
+    pub fn verb(&self) -> &'static str {
+        match self {
+            BulkAction::Complete => "Completed",
+            BulkAction::Reopen => "Reopened",
+            BulkAction::Delete => "Deleted",
+            BulkAction::MoveToList(_) => "Moved",
+            BulkAction::AddTag(_) => "Tagged",
+            BulkAction::SetPriority(_) => "Updated",
+        }
+    }
+
+    /// Update applying the action to `todo`; `None` for [`BulkAction::Delete`].
+    pub fn update_for(&self, todo: &TodoResponse, today: NaiveDate) -> Option<UpdateTodoType> {
+        let update = match self {
+            BulkAction::Delete => return None,
+            // recurring todos move on to their next occurrence, as with a single toggle
+            BulkAction::Complete => todo.completion_update(today),
+            BulkAction::Reopen => UpdateTodoType {
+                completed: Some(false),
+                status: Some(Status::PENDING),
+                ..todo.to_update()
+            },
+            BulkAction::MoveToList(list_id) => UpdateTodoType {
+                list_id: Some(*list_id),
+                ..todo.to_update()
+            },
+            BulkAction::AddTag(tag) => {
+                let mut tags = todo.tags.clone();
+                if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.clone());
+                }
+                UpdateTodoType {
+                    tags: Some(tags),
+                    ..todo.to_update()
+                }
+            }
+            BulkAction::SetPriority(priority) => UpdateTodoType {
+                priority: Some(*priority),
+                ..todo.to_update()
+            },
+        };
+        Some(update)
+    }
+}
+
+/// Outcome of [`apply_batch`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BatchReport {
+    pub total: usize,
+    /// Todos echoed back by the backend after their update.
+    pub saved: Vec<TodoResponse>,
+    /// Todos the backend refused, with the reason.
+    pub failed: Vec<(i64, ApiError)>,
+}
+
+impl BatchReport {
+    /// Combined message such as "Completed 4 of 5 todos. 1 failed: ...".
+    pub fn summary(&self, verb: &str) -> String {
+        let done = self.total - self.failed.len();
+        let noun = if self.total == 1 { "todo" } else { "todos" };
+        match self.failed.first() {
+            None => format!("{} {} {}.", verb, self.total, noun),
+            Some((_, err)) => format!(
+                "{} {} of {} {}. {} failed: {}",
+                verb,
+                done,
+                self.total,
+                noun,
+                self.failed.len(),
+                err
+            ),
+        }
+    }
+}
+
+/// Sends `batch` as one request, falling back to concurrent single requests
+/// when the backend has no batch endpoint.
+///
+/// A refused batch request fails every todo in it with the same error.
+pub async fn apply_batch(client: &TodoApiClient, batch: &TodoBatch) -> BatchReport {
+    let total = batch.len();
+    match client.batch_todos(batch).await {
+        Ok(saved) => BatchReport {
+            total,
+            saved,
+            failed: Vec::new(),
+        },
+        Err(ApiError::Unsupported(_) | ApiError::NotFound(_)) => apply_each(client, batch).await,
+        Err(err) => BatchReport {
+            total,
+            saved: Vec::new(),
+            failed: batch
+                .update
+                .iter()
+                .filter_map(|update| update.id)
+                .chain(batch.delete.iter().copied())
+                .map(|id| (id, err.clone()))
+                .collect(),
+        },
+    }
+}
+
+async fn apply_each(client: &TodoApiClient, batch: &TodoBatch) -> BatchReport {
+    let updates = join_all(batch.update.iter().map(|update| async move {
+        (update.id.unwrap_or_default(), client.update_todo(update).await)
+    }));
+    let deletes = join_all(
+        batch
+            .delete
+            .iter()
+            .map(|id| async move { (*id, client.delete_todo(*id).await) }),
+    );
+    let (updates, deletes) = futures::join!(updates, deletes);
+
+    let mut report = BatchReport {
+        total: batch.len(),
+        ..BatchReport::default()
+    };
+    for (id, result) in updates {
+        match result {
+            Ok(saved) => report.saved.extend(saved),
+            Err(err) => report.failed.push((id, err)),
+        }
+    }
+    for (id, result) in deletes {
+        match result {
+            // already gone on the server, which is what we wanted
+            Ok(()) | Err(ApiError::NotFound(_)) => {}
+            Err(err) => report.failed.push((id, err)),
+        }
+    }
+    report
+}
+
+/// Todos [`create_all`] sends at once.
+pub const CREATE_CHUNK_SIZE: usize = 5;
+
+/// Creates every todo, a few concurrent requests at a time; results are in
+/// the order of `todos`.
+///
+/// A rejected token stops the run: the todos not sent yet get the same error
+/// rather than one refresh attempt each.
+pub async fn create_all(
+    client: &TodoApiClient,
+    todos: &[Todo],
+) -> Vec<Result<Option<TodoResponse>, ApiError>> {
+    let mut results = Vec::with_capacity(todos.len());
+    for chunk in todos.chunks(CREATE_CHUNK_SIZE) {
+        results.extend(join_all(chunk.iter().map(|todo| client.create_todo(todo))).await);
+        let unauthorized = results
+            .iter()
+            .find_map(|result| result.as_ref().err().filter(|err| matches!(err, ApiError::Unauthorized(_))))
+            .cloned();
+        if let Some(err) = unauthorized {
+            results.resize(todos.len(), Err(err));
+            break;
+        }
+    }
+    results
+}
@@ -0,0 +1,330 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use gloo_net::http::{Method, Request, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use yew::Callback;
+
+use crate::api::batch::TodoBatch;
+use crate::api::error::ApiError;
+use crate::api::models::{
+    ListResponse, LoginRequest, LoginResponse, NewList, RefreshRequest, RegisterRequest, Todo,
+    TodoPage, TodoPosition, TodoResponse, UpdateListType, UpdateTodoType,
+};
+use crate::utils::api_url;
+
+const TODO_PATH: &str = "api/v1/todo";
+const REORDER_PATH: &str = "api/v1/todo/reorder";
+const BATCH_PATH: &str = "api/v1/todo/batch";
+const LISTS_PATH: &str = "api/v1/lists";
+const LOGIN_PATH: &str = "api/v1/login";
+const REGISTER_PATH: &str = "api/v1/register";
+const REFRESH_PATH: &str = "api/v1/refresh";
+
+/// Number of todos requested per page.
+pub const PAGE_SIZE: usize = 50;
+
+/// Paged list responses may be a bare array or `{ "items": [..], "total": n }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PageBody {
+    Page(TodoPage),
+    List(Vec<TodoResponse>),
+}
+
+type RefreshFuture = Shared<LocalBoxFuture<'static, Result<LoginResponse, ApiError>>>;
+
+#[derive(Default)]
+struct Session {
+    token: Option<String>,
+    refresh_token: Option<String>,
+    /// The refresh in flight; requests failing with 401 meanwhile wait for it
+    /// instead of spending the refresh token again.
+    refreshing: Option<RefreshFuture>,
+}
+
+/// Tokens of the logged-in user, shared by every [`TodoApiClient`] made for them
+/// so that concurrent 401s lead to a single refresh.
+#[derive(Clone, Default)]
+pub struct SharedSession(Rc<RefCell<Session>>);
+
+impl SharedSession {
+    /// Takes over the tokens of the auth state; a refresh in flight keeps running.
+    pub fn set_tokens(&self, token: Option<String>, refresh_token: Option<String>) {
+        let mut session = self.0.borrow_mut();
+        session.token = token;
+        session.refresh_token = refresh_token;
+    }
+
+    fn token(&self) -> Option<String> {
+        self.0.borrow().token.clone()
+    }
+}
+
+impl PartialEq for SharedSession {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for SharedSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedSession")
+            .field("refreshing", &self.0.borrow().refreshing.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Thin typed wrapper around the backend REST API.
+///
+/// Every method attaches the bearer token (when present) and maps
+/// non-success responses to an [`ApiError`]. When a refresh token is
+/// configured, a 401 triggers one refresh and the request is retried once.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TodoApiClient {
+    session: SharedSession,
+    on_refresh: Option<Callback<LoginResponse>>,
+}
+
+impl TodoApiClient {
+    /// Client using `session`, refreshing it on 401; `on_refresh` receives the new tokens.
+    pub fn for_session(session: SharedSession, on_refresh: Callback<LoginResponse>) -> Self {
+        Self {
+            session,
+            on_refresh: Some(on_refresh),
+        }
+    }
+
+    /// One page of todos, limited to `list_id` when given.
+    pub async fn list_todos_page(
+        &self,
+        offset: usize,
+        limit: usize,
+        list_id: Option<i64>,
+    ) -> Result<TodoPage, ApiError> {
+        let mut url = format!("{}?limit={}&offset={}", api_url(TODO_PATH), limit, offset);
+        if let Some(list_id) = list_id {
+            url.push_str(&format!("&list_id={}", list_id));
+        }
+        let response = self.execute::<()>(Method::GET, &url, None).await?;
+        Ok(match decode::<PageBody>(response).await? {
+            PageBody::Page(page) => page,
+            PageBody::List(items) => TodoPage { items, total: None },
+        })
+    }
+
+    /// Every todo of `list_id` (or of all lists), read page by page.
+    pub async fn list_all_todos(&self, list_id: Option<i64>) -> Result<Vec<TodoResponse>, ApiError> {
+        let mut items: Vec<TodoResponse> = Vec::new();
+        loop {
+            let page = self.list_todos_page(items.len(), PAGE_SIZE, list_id).await?;
+            let count = page.items.len();
+            items.extend(page.items);
+            if count < PAGE_SIZE || page.total.is_some_and(|total| items.len() >= total) {
+                return Ok(items);
+            }
+        }
+    }
+
+    pub async fn get_todo(&self, id: i64) -> Result<TodoResponse, ApiError> {
+        let url = api_url(&format!("{}/{}", TODO_PATH, id));
+        let response = self.execute::<()>(Method::GET, &url, None).await?;
+        decode(response).await
+    }
+
+    /// Returns the created todo when the backend echoes it in the response body.
+    pub async fn create_todo(&self, todo: &Todo) -> Result<Option<TodoResponse>, ApiError> {
+        let response = self.execute(Method::POST, &api_url(TODO_PATH), Some(todo)).await?;
+        Ok(decode_echo(response).await)
+    }
+
+    /// Returns the updated todo when the backend echoes it in the response body.
+    pub async fn update_todo(&self, update: &UpdateTodoType) -> Result<Option<TodoResponse>, ApiError> {
+        let response = self.execute(Method::PUT, &api_url(TODO_PATH), Some(update)).await?;
+        Ok(decode_echo(response).await)
+    }
+
+    pub async fn reorder_todos(&self, moves: &[TodoPosition]) -> Result<(), ApiError> {
+        self.execute(Method::PUT, &api_url(REORDER_PATH), Some(&moves)).await?;
+        Ok(())
+    }
+
+    /// Applies every change of `batch` at once; returns the updated todos when
+    /// the backend echoes them.
+    pub async fn batch_todos(&self, batch: &TodoBatch) -> Result<Vec<TodoResponse>, ApiError> {
+        let response = self.execute(Method::POST, &api_url(BATCH_PATH), Some(batch)).await?;
+        Ok(decode_echo(response).await.unwrap_or_default())
+    }
+
+    pub async fn delete_todo(&self, id: i64) -> Result<(), ApiError> {
+        let url = api_url(&format!("{}/{}", TODO_PATH, id));
+        self.execute::<()>(Method::DELETE, &url, None).await?;
+        Ok(())
+    }
+
+    pub async fn list_lists(&self) -> Result<Vec<ListResponse>, ApiError> {
+        let response = self.execute::<()>(Method::GET, &api_url(LISTS_PATH), None).await?;
+        decode(response).await
+    }
+
+    /// Returns the created list when the backend echoes it in the response body.
+    pub async fn create_list(&self, list: &NewList) -> Result<Option<ListResponse>, ApiError> {
+        let response = self.execute(Method::POST, &api_url(LISTS_PATH), Some(list)).await?;
+        Ok(decode_echo(response).await)
+    }
+
+    pub async fn update_list(&self, update: &UpdateListType) -> Result<(), ApiError> {
+        self.execute(Method::PUT, &api_url(LISTS_PATH), Some(update)).await?;
+        Ok(())
+    }
+
+    pub async fn delete_list(&self, id: i64) -> Result<(), ApiError> {
+        let url = api_url(&format!("{}/{}", LISTS_PATH, id));
+        self.execute::<()>(Method::DELETE, &url, None).await?;
+        Ok(())
+    }
+
+    pub async fn login(&self, body: &LoginRequest) -> Result<LoginResponse, ApiError> {
+        let response = send_json(Request::post(&api_url(LOGIN_PATH)), body).await?;
+        decode(response).await
+    }
+
+    pub async fn register(&self, body: &RegisterRequest) -> Result<(), ApiError> {
+        send_json(Request::post(&api_url(REGISTER_PATH)), body).await?;
+        Ok(())
+    }
+
+    /// Exchanges the refresh token for a new access token, or waits for the
+    /// exchange another request of the same session already started.
+    ///
+    /// The previous refresh token is kept when the backend does not rotate it.
+    pub async fn refresh_session(&self) -> Result<LoginResponse, ApiError> {
+        let pending = self.session.0.borrow().refreshing.clone();
+        let refresh = match pending {
+            Some(refresh) => refresh,
+            None => {
+                let refresh_token = self
+                    .session
+                    .0
+                    .borrow()
+                    .refresh_token
+                    .clone()
+                    .ok_or(ApiError::Unauthorized(None))?;
+                let refresh = exchange_refresh_token(refresh_token).boxed_local().shared();
+                self.session.0.borrow_mut().refreshing = Some(refresh.clone());
+                refresh
+            }
+        };
+        let result = refresh.clone().await;
+
+        // the first waiter back stores the outcome and tells the app, once
+        let first = {
+            let mut session = self.session.0.borrow_mut();
+            let first = session
+                .refreshing
+                .as_ref()
+                .is_some_and(|current| current.ptr_eq(&refresh));
+            if first {
+                session.refreshing = None;
+                if let Ok(tokens) = &result {
+                    session.token = Some(tokens.token.clone());
+                    session.refresh_token = tokens.refresh_token.clone();
+                }
+            }
+            first
+        };
+        if let (true, Ok(tokens), Some(on_refresh)) = (first, &result, &self.on_refresh) {
+            on_refresh.emit(tokens.clone());
+        }
+        result
+    }
+
+    async fn execute<B: Serialize>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&B>,
+    ) -> Result<Response, ApiError> {
+        let sent_with = self.session.token();
+        match self.send_once(method.clone(), url, body).await {
+            Err(ApiError::Unauthorized(message)) if self.on_refresh.is_some() => {
+                // another request may have renewed the token while this one was out
+                if self.session.token() != sent_with {
+                    return self.send_once(method, url, body).await;
+                }
+                match self.refresh_session().await {
+                    Ok(_) => self.send_once(method, url, body).await,
+                    Err(_) => Err(ApiError::Unauthorized(message)),
+                }
+            }
+            result => result,
+        }
+    }
+
+    async fn send_once<B: Serialize>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&B>,
+    ) -> Result<Response, ApiError> {
+        let builder = self.authorized(RequestBuilder::new(url).method(method));
+        match body {
+            Some(body) => send_json(builder, body).await,
+            None => send(builder).await,
+        }
+    }
+
+    fn authorized(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.session.0.borrow().token {
+            Some(token) => builder.header("Authorization", &format!("Bearer {}", token)),
+            None => builder,
+        }
+    }
+}
+
+async fn exchange_refresh_token(refresh_token: String) -> Result<LoginResponse, ApiError> {
+    let body = RefreshRequest {
+        refresh_token: refresh_token.clone(),
+    };
+    let response = send_json(Request::post(&api_url(REFRESH_PATH)), &body).await?;
+    let mut tokens: LoginResponse = decode(response).await?;
+    tokens.refresh_token.get_or_insert(refresh_token);
+    Ok(tokens)
+}
+
+async fn send(builder: RequestBuilder) -> Result<Response, ApiError> {
+    check_status(builder.send().await?).await
+}
+
+async fn send_json<B: Serialize>(builder: RequestBuilder, body: &B) -> Result<Response, ApiError> {
+    let request = builder
+        .header("Content-Type", "application/json")
+        .json(body)?;
+    check_status(request.send().await?).await
+}
+
+async fn check_status(response: Response) -> Result<Response, ApiError> {
+    if response.ok() {
+        Ok(response)
+    } else {
+        Err(ApiError::from_response(response).await)
+    }
+}
+
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    response
+        .json::<T>()
+        .await
+        .map_err(|err| ApiError::Decode(err.to_string()))
+}
+
+/// Decodes an optional echoed body; empty or non-JSON bodies yield `None`.
+async fn decode_echo<T: DeserializeOwned>(response: Response) -> Option<T> {
+    response
+        .text()
+        .await
+        .ok()
+        .and_then(|body| serde_json::from_str(&body).ok())
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+
+use gloo_net::http::Response;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A single invalid field reported by the backend.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Failure returned by every [`TodoApiClient`](crate::api::client::TodoApiClient) call.
+///
+/// Variants carrying an `Option<String>` hold the message the backend sent
+/// in its JSON error body, if any.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ApiError {
+    /// The request could not be built or the server could not be reached.
+    Network(String),
+    /// 401 – missing, expired or rejected credentials.
+    Unauthorized(Option<String>),
+    /// 403 – authenticated but not allowed.
+    Forbidden(Option<String>),
+    /// 404 – the resource does not exist (anymore).
+    NotFound(Option<String>),
+    /// 400/422 and other client errors, with per-field details when provided.
+    Validation {
+        message: Option<String>,
+        fields: Vec<FieldError>,
+    },
+    /// 409 – the resource was changed or already exists.
+    Conflict(Option<String>),
+    /// 405/501 – the backend does not offer this endpoint or method.
+    Unsupported(Option<String>),
+    /// 5xx and any other unexpected status.
+    Server { status: u16, message: Option<String> },
+    /// The response body did not match the expected shape.
+    Decode(String),
+}
+
+#[derive(Deserialize, Default)]
+struct ErrorBody {
+    #[serde(default, alias = "error", alias = "detail")]
+    message: Option<String>,
+    #[serde(default)]
+    errors: Option<Value>,
+}
+
+impl ApiError {
+    /// Builds the error matching a non-success response, reading the JSON
+    /// error body when the backend sent one.
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .ok()
+            .and_then(|text| serde_json::from_str::<ErrorBody>(&text).ok())
+            .unwrap_or_default();
+        Self::from_status(status, body.message, body.errors.as_ref().map(parse_fields))
+    }
+
+    fn from_status(status: u16, message: Option<String>, fields: Option<Vec<FieldError>>) -> Self {
+        let message = message.filter(|m| !m.trim().is_empty());
+        match status {
+            401 => ApiError::Unauthorized(message),
+            403 => ApiError::Forbidden(message),
+            404 => ApiError::NotFound(message),
+            409 => ApiError::Conflict(message),
+            405 | 501 => ApiError::Unsupported(message),
+            400..=499 => ApiError::Validation {
+                message,
+                fields: fields.unwrap_or_default(),
+            },
+            _ => ApiError::Server { status, message },
+        }
+    }
+}
+
+/// Accepts both `{"field": "msg" | ["msg", ..]}` and `[{"field": .., "message": ..}]`.
+fn parse_fields(errors: &Value) -> Vec<FieldError> {
+    match errors {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(field, value)| {
+                let message = match value {
+                    Value::String(msg) => msg.clone(),
+                    Value::Array(items) => items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    Value::Object(obj) => obj.get("message")?.as_str()?.to_string(),
+                    _ => return None,
+                };
+                Some(FieldError {
+                    field: field.clone(),
+                    message,
+                })
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                Some(FieldError {
+                    field: item.get("field")?.as_str()?.to_string(),
+                    message: item.get("message")?.as_str()?.to_string(),
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(_) => {
+                write!(f, "Cannot connect to server. Check your connection and try again.")
+            }
+            ApiError::Unauthorized(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("Your session has expired. Please log in again.")
+            ),
+            ApiError::Forbidden(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("You don't have permission to do that.")
+            ),
+            ApiError::NotFound(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("The requested item no longer exists.")
+            ),
+            ApiError::Validation { message, fields } => {
+                write!(f, "{}", message.as_deref().unwrap_or("Some fields are invalid."))?;
+                for field in fields {
+                    write!(f, " {}: {}.", field.field, field.message)?;
+                }
+                Ok(())
+            }
+            ApiError::Conflict(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("This item was changed elsewhere. Refresh and try again.")
+            ),
+            ApiError::Unsupported(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("The server does not support this action.")
+            ),
+            ApiError::Server { status, message } => match message {
+                Some(message) => write!(f, "Server error ({}): {}", status, message),
+                None => write!(
+                    f,
+                    "The server encountered an error ({}). Please try again later.",
+                    status
+                ),
+            },
+            ApiError::Decode(_) => {
+                write!(f, "Received an unexpected response from the server.")
+            }
+        }
+    }
+}
+
+impl From<gloo_net::Error> for ApiError {
+    fn from(err: gloo_net::Error) -> Self {
+        ApiError::Network(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(field: &str, message: &str) -> FieldError {
+        FieldError {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_field_maps() {
+        let errors = json!({
+            "title": "must not be empty",
+            "due_date": ["is not a date", "is in the past"],
+            "tags": {"message": "too many tags"},
+            "priority": 3,
+        });
+        assert_eq!(
+            parse_fields(&errors),
+            vec![
+                field("due_date", "is not a date, is in the past"),
+                field("tags", "too many tags"),
+                field("title", "must not be empty"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_field_lists() {
+        let errors = json!([
+            {"field": "title", "message": "must not be empty"},
+            {"message": "no field"},
+            {"field": "priority", "message": 3},
+        ]);
+        assert_eq!(parse_fields(&errors), vec![field("title", "must not be empty")]);
+        assert!(parse_fields(&json!("bad request")).is_empty());
+    }
+
+    #[test]
+    fn fields_only_come_with_client_errors() {
+        let fields = Some(vec![field("title", "must not be empty")]);
+        assert_eq!(
+            ApiError::from_status(422, Some(" ".to_string()), fields.clone()),
+            ApiError::Validation {
+                message: None,
+                fields: vec![field("title", "must not be empty")],
+            }
+        );
+        assert_eq!(
+            ApiError::from_status(500, None, fields),
+            ApiError::Server {
+                status: 500,
+                message: None
+            }
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod batch;
+pub mod client;
+pub mod error;
+pub mod models;
+pub mod sync;
@@ -0,0 +1,531 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::types::Status;
+use crate::utils::recurrence::Recurrence;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Urgent,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+            Priority::Urgent => "Urgent",
+        }
+    }
+
+    pub fn from_str(priority: &str) -> Self {
+        match priority {
+            "low" => Priority::Low,
+            "high" => Priority::High,
+            "urgent" => Priority::Urgent,
+            _ => Priority::Medium,
+        }
+    }
+}
+
+/// Local date and, for a date-time, time of day of a due date stored as
+/// `YYYY-MM-DD` or RFC 3339; both come from the same local instant.
+fn parse_due(due: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    if due.len() > 10 {
+        if let Ok(date) = DateTime::parse_from_rfc3339(due) {
+            let local = date.with_timezone(&Local);
+            return Some((local.date_naive(), Some(local.time())));
+        }
+        if let Ok(date) = NaiveDateTime::parse_from_str(due, "%Y-%m-%dT%H:%M:%S") {
+            return Some((date.date(), Some(date.time())));
+        }
+    }
+    due.get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .map(|date| (date, None))
+}
+
+/// Local date of a due date stored as `YYYY-MM-DD` or an RFC 3339 date-time.
+pub fn parse_due_date(due: &str) -> Option<NaiveDate> {
+    parse_due(due).map(|(date, _)| date)
+}
+
+/// Local time of day of a due date-time; `None` for a plain `YYYY-MM-DD`.
+pub fn parse_due_time(due: &str) -> Option<NaiveTime> {
+    parse_due(due).and_then(|(_, time)| time)
+}
+
+/// Due date in the form todos store: `YYYY-MM-DD`, or RFC 3339 in the local
+/// timezone when a time of day is set.
+pub fn format_due(date: NaiveDate, time: Option<NaiveTime>) -> String {
+    let Some(time) = time else {
+        return date.format("%Y-%m-%d").to_string();
+    };
+    let local = date.and_time(time);
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_else(|| local.format("%Y-%m-%dT%H:%M:%S").to_string())
+}
+
+/// One entry of a todo's checklist; `id` is unique within that todo.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ChecklistItem {
+    pub id: u32,
+    pub title: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// `(done, total)` for a non-empty checklist.
+pub fn checklist_progress(items: &[ChecklistItem]) -> Option<(usize, usize)> {
+    (!items.is_empty()).then(|| (items.iter().filter(|item| item.done).count(), items.len()))
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Todo {
+    pub title: String,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `YYYY-MM-DD` or an RFC 3339 date-time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// List the todo belongs to; `None` keeps it out of every list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    /// RRULE-style repeat schedule, see [`Recurrence`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TodoResponse {
+    pub id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub completed: bool,
+    /// Richer state than `completed`; backends without it only send `completed`.
+    #[serde(default)]
+    pub status: Option<Status>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ordered checklist items.
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// Place in the manual order; lower comes first, unset goes last.
+    #[serde(default)]
+    pub position: Option<f64>,
+    #[serde(default)]
+    pub list_id: Option<i64>,
+    /// RRULE-style repeat schedule, see [`Recurrence`].
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl TodoResponse {
+    pub fn due(&self) -> Option<NaiveDate> {
+        self.due_date.as_deref().and_then(parse_due_date)
+    }
+
+    pub fn due_time(&self) -> Option<NaiveTime> {
+        self.due_date.as_deref().and_then(parse_due_time)
+    }
+
+    /// `status` when the backend sent one, otherwise derived from `completed`.
+    pub fn status(&self) -> Status {
+        match self.status {
+            Some(status) => status,
+            None if self.completed => Status::DONE,
+            None => Status::PENDING,
+        }
+    }
+
+    /// Local stand-in for a todo the backend has not confirmed yet.
+    pub fn unsaved(id: i64, todo: &Todo, now: &str) -> Self {
+        TodoResponse {
+            id,
+            title: todo.title.clone(),
+            description: todo.description.clone(),
+            completed: todo.completed,
+            status: todo.status,
+            due_date: todo.due_date.clone(),
+            priority: todo.priority,
+            tags: todo.tags.clone(),
+            checklist: todo.checklist.clone(),
+            position: None,
+            list_id: todo.list_id,
+            recurrence: todo.recurrence.clone(),
+            created_at: now.to_string(),
+            updated_at: now.to_string(),
+        }
+    }
+
+    /// Applies `update` the way the backend would, for optimistic rendering.
+    pub fn patched(&self, update: &UpdateTodoType) -> Self {
+        let mut todo = self.clone();
+        if let Some(title) = &update.title {
+            todo.title = title.clone();
+        }
+        if update.description.is_some() {
+            todo.description = update.description.clone();
+        }
+        if let Some(completed) = update.completed {
+            todo.completed = completed;
+        }
+        // `status` wins over `completed` and keeps both in step
+        if let Some(status) = update.status {
+            todo.status = Some(status);
+            todo.completed = status == Status::DONE;
+        }
+        if let Some(due_date) = &update.due_date {
+            todo.due_date = (!due_date.is_empty()).then(|| due_date.clone());
+        }
+        if let Some(priority) = update.priority {
+            todo.priority = priority;
+        }
+        if let Some(tags) = &update.tags {
+            todo.tags = tags.clone();
+        }
+        if let Some(checklist) = &update.checklist {
+            todo.checklist = checklist.clone();
+        }
+        if let Some(recurrence) = &update.recurrence {
+            todo.recurrence = (!recurrence.is_empty()).then(|| recurrence.clone());
+        }
+        if let Some(list_id) = update.list_id {
+            todo.list_id = list_id;
+        }
+        todo
+    }
+
+    pub fn repeats(&self) -> Option<Recurrence> {
+        self.recurrence.as_deref().and_then(Recurrence::parse)
+    }
+
+    /// Update that completes the todo, or for a recurring todo, moves it on to
+    /// its next occurrence: reopened, due on the next date at the same time of
+    /// day, checklist unticked.
+    pub fn completion_update(&self, today: NaiveDate) -> UpdateTodoType {
+        match self.repeats() {
+            Some(recurrence) => UpdateTodoType {
+                completed: Some(false),
+                status: Some(Status::PENDING),
+                due_date: Some(format_due(
+                    recurrence.next_due(self.due(), today),
+                    self.due_time(),
+                )),
+                checklist: Some(
+                    self.checklist
+                        .iter()
+                        .map(|item| ChecklistItem {
+                            done: false,
+                            ..item.clone()
+                        })
+                        .collect(),
+                ),
+                ..self.to_update()
+            },
+            None => UpdateTodoType {
+                completed: Some(true),
+                status: Some(Status::DONE),
+                ..self.to_update()
+            },
+        }
+    }
+
+    /// Full update carrying every field, so nothing is cleared by omission.
+    pub fn to_update(&self) -> UpdateTodoType {
+        UpdateTodoType {
+            id: Some(self.id),
+            title: Some(self.title.clone()),
+            description: self.description.clone(),
+            completed: Some(self.completed),
+            status: Some(self.status()),
+            due_date: Some(self.due_date.clone().unwrap_or_default()),
+            priority: Some(self.priority),
+            tags: Some(self.tags.clone()),
+            checklist: Some(self.checklist.clone()),
+            recurrence: Some(self.recurrence.clone().unwrap_or_default()),
+            list_id: None,
+        }
+    }
+}
+
+/// One page of `GET api/v1/todo?limit=..&offset=..`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct TodoPage {
+    pub items: Vec<TodoResponse>,
+    /// Total number of todos, when the backend reports it.
+    #[serde(default)]
+    pub total: Option<usize>,
+}
+
+/// New place of one todo in the manual order, sent to `PUT api/v1/todo/reorder`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TodoPosition {
+    pub id: i64,
+    pub position: f64,
+}
+
+/// Change pushed by the backend over the live channel (`api/v1/todo/live`).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TodoEvent {
+    Created { todo: TodoResponse },
+    Updated { todo: TodoResponse },
+    Deleted { id: i64 },
+}
+
+/// Partial update sent to `PUT api/v1/todo`.
+///
+/// `status`, `due_date`, `priority`, `tags` and `checklist` are omitted when `None`;
+/// send an empty string / empty list to clear them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct UpdateTodoType {
+    pub id: Option<i64>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub completed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Replaces the whole checklist, order included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checklist: Option<Vec<ChecklistItem>>,
+    /// Repeat rule; an empty string stops the todo from repeating.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+    /// Moves the todo to another list; `Some(None)` (sent as `null`) takes it out of every list.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub list_id: Option<Option<i64>>,
+}
+
+/// Keeps an explicit `null` apart from a missing field.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Body of `POST api/v1/lists`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NewList {
+    pub name: String,
+}
+
+/// A named todo list such as "Work" or "Sprint 42".
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ListResponse {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub archived: bool,
+    /// Counts reported by the backend, if any.
+    #[serde(default)]
+    pub pending: Option<usize>,
+    #[serde(default)]
+    pub completed: Option<usize>,
+}
+
+/// Active list named `name`, ignoring case and surrounding spaces and treating `-`/`_` as spaces.
+pub fn find_list<'a>(lists: &'a [ListResponse], name: &str) -> Option<&'a ListResponse> {
+    let wanted = name.trim().replace(['-', '_'], " ");
+    lists
+        .iter()
+        .find(|list| !list.archived && list.name.replace(['-', '_'], " ").eq_ignore_ascii_case(&wanted))
+}
+
+/// Partial update sent to `PUT api/v1/lists` (rename or archive).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct UpdateListType {
+    pub id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LoginResponse {
+    pub token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RegisterRequest {
+    pub username: String,
+    pub password: String,
+}
+
+/// Todos shared by the import/export tests.
+#[cfg(test)]
+pub mod fixtures {
+    use super::*;
+
+    pub fn todo(id: i64, title: &str) -> TodoResponse {
+        TodoResponse {
+            id,
+            title: title.to_string(),
+            description: None,
+            completed: false,
+            status: None,
+            due_date: None,
+            priority: Priority::Medium,
+            tags: Vec::new(),
+            checklist: Vec::new(),
+            position: None,
+            list_id: None,
+            recurrence: None,
+            created_at: "2024-05-01T10:00:00Z".to_string(),
+            updated_at: "2024-05-02T08:30:00Z".to_string(),
+        }
+    }
+
+    /// A repeating todo with a checklist, a done one and one in progress.
+    pub fn sample_todos() -> Vec<TodoResponse> {
+        vec![
+            TodoResponse {
+                description: Some("Bring the \"big\" box,\nand the tape".to_string()),
+                due_date: Some("2024-05-03".to_string()),
+                priority: Priority::High,
+                tags: vec!["home".to_string(), "errands".to_string()],
+                checklist: vec![
+                    ChecklistItem {
+                        id: 1,
+                        title: "Tape".to_string(),
+                        done: true,
+                    },
+                    ChecklistItem {
+                        id: 2,
+                        title: "Box".to_string(),
+                        done: false,
+                    },
+                ],
+                list_id: Some(7),
+                recurrence: Some("FREQ=WEEKLY;INTERVAL=1;BYDAY=FR".to_string()),
+                ..todo(1, "Pack, then ship")
+            },
+            TodoResponse {
+                completed: true,
+                status: Some(Status::DONE),
+                priority: Priority::Low,
+                ..todo(2, "Pay rent")
+            },
+            TodoResponse {
+                status: Some(Status::IN_PROGRESS),
+                priority: Priority::Urgent,
+                ..todo(3, "Write report")
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_date_and_time_come_from_the_same_local_instant() {
+        // 23:30 at UTC-5 is already the next day in UTC and further east
+        let dues = ["2024-05-03T23:30:00Z", "2024-05-03T23:30:00-05:00", "2024-05-03T00:30:00+09:00"];
+        for due in dues {
+            let local = DateTime::parse_from_rfc3339(due).unwrap().with_timezone(&Local);
+            assert_eq!(parse_due_date(due), Some(local.date_naive()));
+            assert_eq!(parse_due_time(due), Some(local.time()));
+            // saving the edit form's fields keeps the same moment
+            let saved = format_due(local.date_naive(), Some(local.time()));
+            let instant = |value: &str| DateTime::parse_from_rfc3339(value).ok();
+            assert_eq!(instant(&saved), instant(due));
+        }
+        assert_eq!(
+            parse_due_date("2024-05-03T23:30:00"),
+            NaiveDate::from_ymd_opt(2024, 5, 3)
+        );
+    }
+
+    #[test]
+    fn due_time_survives_edits_and_repeats() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+        let five_pm = NaiveTime::from_hms_opt(17, 0, 0);
+        assert_eq!(format_due(date, None), "2024-05-03");
+        assert_eq!(parse_due_time("2024-05-03"), None);
+
+        let due = format_due(date, five_pm);
+        assert_eq!(parse_due_date(&due), Some(date));
+        assert_eq!(parse_due_time(&due), five_pm);
+
+        let todo = Todo {
+            title: "Call mom".to_string(),
+            due_date: Some(due),
+            recurrence: Some("FREQ=DAILY;INTERVAL=1".to_string()),
+            ..Todo::default()
+        };
+        let todo = TodoResponse::unsaved(1, &todo, "2024-05-01T10:00:00Z");
+        let next = todo.completion_update(date).due_date.unwrap();
+        assert_eq!(parse_due_date(&next), date.succ_opt());
+        assert_eq!(parse_due_time(&next), five_pm);
+    }
+}
@@ -0,0 +1,429 @@
+//! Offline support: a per-user cache of the todo list and a queue of changes
+//! made while the backend was unreachable, replayed in order once it is back.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::client::TodoApiClient;
+use crate::api::error::ApiError;
+use crate::api::models::{Todo, TodoResponse, UpdateTodoType};
+use crate::utils::storage::{load_json, store_json};
+
+const CACHE_KEY: &str = "todo_cache";
+const QUEUE_KEY: &str = "todo_sync_queue";
+
+fn user_key(prefix: &str, user: &str) -> String {
+    format!("{}:{}", prefix, user)
+}
+
+/// Key of the cached todos of `user`, one cache per list.
+pub fn cache_key(user: &str, list_id: Option<i64>) -> String {
+    match list_id {
+        Some(id) => format!("{}/list-{}", user, id),
+        None => user.to_string(),
+    }
+}
+
+/// Todos last seen under `key` (see [`cache_key`]), rendered while offline.
+pub fn load_cache(key: &str) -> Vec<TodoResponse> {
+    load_json(&user_key(CACHE_KEY, key)).unwrap_or_default()
+}
+
+pub fn store_cache(key: &str, todos: &[TodoResponse]) {
+    store_json(&user_key(CACHE_KEY, key), &todos);
+}
+
+/// A change made locally that the backend has not seen yet.
+///
+/// Todos created offline keep a negative local id until the replay assigns
+/// the real one. `base_updated_at` is the `updated_at` the change was based on.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PendingChange {
+    Create {
+        local_id: i64,
+        todo: Todo,
+    },
+    Update {
+        update: UpdateTodoType,
+        base_updated_at: String,
+    },
+    Delete {
+        id: i64,
+        title: String,
+        base_updated_at: String,
+    },
+}
+
+impl PendingChange {
+    pub fn todo_id(&self) -> i64 {
+        match self {
+            PendingChange::Create { local_id, .. } => *local_id,
+            PendingChange::Update { update, .. } => update.id.unwrap_or_default(),
+            PendingChange::Delete { id, .. } => *id,
+        }
+    }
+
+    fn title(&self) -> &str {
+        match self {
+            PendingChange::Create { todo, .. } => &todo.title,
+            PendingChange::Update { update, .. } => update.title.as_deref().unwrap_or("A todo"),
+            PendingChange::Delete { title, .. } => title,
+        }
+    }
+
+    fn with_id(mut self, id: i64) -> Self {
+        match &mut self {
+            PendingChange::Create { local_id, .. } => *local_id = id,
+            PendingChange::Update { update, .. } => update.id = Some(id),
+            PendingChange::Delete { id: todo_id, .. } => *todo_id = id,
+        }
+        self
+    }
+}
+
+/// Result of [`replay`].
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub applied: usize,
+    /// User-facing reasons for changes that were dropped, e.g. conflicts.
+    pub discarded: Vec<String>,
+    /// Changes left for the next attempt, in order.
+    pub remaining: Vec<PendingChange>,
+    /// Local ids of todos created offline, paired with their server ids.
+    pub id_map: Vec<(i64, i64)>,
+    /// Set when the replay stopped early (backend unreachable or session ended).
+    pub error: Option<ApiError>,
+}
+
+impl SyncReport {
+    fn server_id(&self, local_id: i64) -> Option<i64> {
+        self.id_map
+            .iter()
+            .find(|(local, _)| *local == local_id)
+            .map(|(_, server)| *server)
+    }
+}
+
+/// Changes waiting to be replayed for one user, persisted in `localStorage`.
+#[derive(Debug, Default)]
+pub struct SyncQueue {
+    key: String,
+    changes: Vec<PendingChange>,
+    replaying: bool,
+}
+
+impl SyncQueue {
+    pub fn load(user: &str) -> Self {
+        let key = user_key(QUEUE_KEY, user);
+        SyncQueue {
+            changes: load_json(&key).unwrap_or_default(),
+            key,
+            replaying: false,
+        }
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// True while changes are waiting or being replayed; new changes must
+    /// then be queued too so they reach the backend in order.
+    pub fn is_busy(&self) -> bool {
+        self.replaying || !self.changes.is_empty()
+    }
+
+    /// True when the todo has changes that are not synced yet.
+    pub fn touches(&self, todo_id: i64) -> bool {
+        self.changes.iter().any(|change| change.todo_id() == todo_id)
+    }
+
+    /// True for a todo created online that is still waiting for its server id:
+    /// changes to it can be neither sent nor queued yet.
+    pub fn is_unsaved(&self, todo_id: i64) -> bool {
+        todo_id < 0 && !self.touches(todo_id)
+    }
+
+    /// Lowest local id in use, so new offline todos don't reuse one after a reload.
+    pub fn min_local_id(&self) -> i64 {
+        self.changes
+            .iter()
+            .map(PendingChange::todo_id)
+            .min()
+            .unwrap_or(0)
+            .min(0)
+    }
+
+    pub fn push(&mut self, change: PendingChange) {
+        match change {
+            // the backend never saw this todo: forget it instead of replaying create + delete
+            PendingChange::Delete { id, .. } if id < 0 && !self.replaying => {
+                self.changes.retain(|change| change.todo_id() != id);
+            }
+            change => self.changes.push(change),
+        }
+        self.save();
+    }
+
+    /// Hands out every queued change for [`replay`]; call [`finish`](Self::finish) afterwards.
+    pub fn take(&mut self) -> Vec<PendingChange> {
+        if self.replaying || self.changes.is_empty() {
+            return Vec::new();
+        }
+        self.replaying = true;
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Puts back what the replay did not get to, ahead of changes queued meanwhile.
+    pub fn finish(&mut self, report: &SyncReport) {
+        let queued_meanwhile = std::mem::take(&mut self.changes);
+        self.changes = requeue(report, queued_meanwhile);
+        self.replaying = false;
+        self.save();
+    }
+
+    fn save(&self) {
+        store_json(&self.key, &self.changes);
+    }
+}
+
+/// The changes [`SyncQueue::finish`] keeps: the replay's leftovers, then those
+/// queued meanwhile, all pointing at the server ids of todos created offline.
+fn requeue(report: &SyncReport, queued_meanwhile: Vec<PendingChange>) -> Vec<PendingChange> {
+    report
+        .remaining
+        .iter()
+        .cloned()
+        .chain(queued_meanwhile)
+        .map(|change| match report.server_id(change.todo_id()) {
+            Some(id) => change.with_id(id),
+            None => change,
+        })
+        .collect()
+}
+
+/// `change` pointed at the server id of a todo the replay created, or `None`
+/// when it still targets an offline todo whose creation did not go through.
+fn resolve(change: PendingChange, report: &SyncReport) -> Option<PendingChange> {
+    let change = match report.server_id(change.todo_id()) {
+        Some(id) => change.with_id(id),
+        None => change,
+    };
+    (matches!(change, PendingChange::Create { .. }) || change.todo_id() >= 0).then_some(change)
+}
+
+/// True when the todo was changed on the server after `base_updated_at`, other
+/// than by this replay (whose `updated_at` values are in `confirmed`).
+fn is_conflict(
+    id: i64,
+    base_updated_at: &str,
+    current_updated_at: &str,
+    confirmed: &HashMap<i64, String>,
+) -> bool {
+    current_updated_at != base_updated_at
+        && confirmed.get(&id).map(String::as_str) != Some(current_updated_at)
+}
+
+/// Sends `changes` to the backend in order.
+///
+/// Updates and deletes of todos that were changed on the server since the
+/// change was made (different `updated_at`) are discarded: the server wins.
+/// The replay stops at the first network or authentication error.
+pub async fn replay(client: &TodoApiClient, changes: Vec<PendingChange>) -> SyncReport {
+    let mut report = SyncReport::default();
+    // updated_at values produced by this replay, so later changes to the same todo aren't conflicts
+    let mut confirmed: HashMap<i64, String> = HashMap::new();
+    let mut changes = changes.into_iter();
+
+    while let Some(change) = changes.next() {
+        let local_id = change.todo_id();
+        let title = change.title().to_string();
+        let Some(change) = resolve(change, &report) else {
+            report.discarded.push(format!(
+                "\"{}\" could not be matched to a saved todo; the offline change was discarded.",
+                title
+            ));
+            continue;
+        };
+
+        match apply(client, &change, local_id < 0, &mut confirmed).await {
+            Ok(Applied::Done) => report.applied += 1,
+            Ok(Applied::Created(Some(todo))) => {
+                let todo = *todo;
+                report.applied += 1;
+                report.id_map.push((local_id, todo.id));
+                confirmed.insert(todo.id, todo.updated_at);
+            }
+            Ok(Applied::Created(None)) => report.applied += 1,
+            Ok(Applied::Conflict) => report.discarded.push(format!(
+                "\"{}\" was changed on another device; the offline change was discarded.",
+                change.title()
+            )),
+            Err(err @ (ApiError::Network(_) | ApiError::Unauthorized(_))) => {
+                report.remaining.push(change);
+                report.remaining.extend(changes);
+                report.error = Some(err);
+                break;
+            }
+            Err(err) => report
+                .discarded
+                .push(format!("\"{}\" could not be synced: {}", change.title(), err)),
+        }
+    }
+    report
+}
+
+enum Applied {
+    Done,
+    Created(Option<Box<TodoResponse>>),
+    Conflict,
+}
+
+async fn apply(
+    client: &TodoApiClient,
+    change: &PendingChange,
+    created_offline: bool,
+    confirmed: &mut HashMap<i64, String>,
+) -> Result<Applied, ApiError> {
+    match change {
+        PendingChange::Create { todo, .. } => {
+            Ok(Applied::Created(client.create_todo(todo).await?.map(Box::new)))
+        }
+        PendingChange::Update {
+            update,
+            base_updated_at,
+        } => {
+            let id = change.todo_id();
+            if !created_offline && changed_since(client, id, base_updated_at, confirmed).await? {
+                return Ok(Applied::Conflict);
+            }
+            let saved = match client.update_todo(update).await? {
+                Some(saved) => saved,
+                None => client.get_todo(id).await?,
+            };
+            confirmed.insert(id, saved.updated_at);
+            Ok(Applied::Done)
+        }
+        PendingChange::Delete {
+            id,
+            base_updated_at,
+            ..
+        } => {
+            if !created_offline {
+                match changed_since(client, *id, base_updated_at, confirmed).await {
+                    Ok(true) => return Ok(Applied::Conflict),
+                    Ok(false) => {}
+                    // already deleted elsewhere
+                    Err(ApiError::NotFound(_)) => return Ok(Applied::Done),
+                    Err(err) => return Err(err),
+                }
+            }
+            match client.delete_todo(*id).await {
+                Ok(()) | Err(ApiError::NotFound(_)) => Ok(Applied::Done),
+                Err(err) => Err(err),
+            }
+        }
+    }
+}
+
+async fn changed_since(
+    client: &TodoApiClient,
+    id: i64,
+    base_updated_at: &str,
+    confirmed: &HashMap<i64, String>,
+) -> Result<bool, ApiError> {
+    let current = client.get_todo(id).await?;
+    Ok(is_conflict(id, base_updated_at, &current.updated_at, confirmed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(id: i64, title: &str) -> PendingChange {
+        PendingChange::Update {
+            update: UpdateTodoType {
+                id: Some(id),
+                title: Some(title.to_string()),
+                ..UpdateTodoType::default()
+            },
+            base_updated_at: "2024-05-01T10:00:00Z".to_string(),
+        }
+    }
+
+    fn delete(id: i64) -> PendingChange {
+        PendingChange::Delete {
+            id,
+            title: "Old".to_string(),
+            base_updated_at: "2024-05-01T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn conflicts_come_from_other_devices_only() {
+        let mut confirmed = HashMap::new();
+        let base = "2024-05-01T10:00:00Z";
+        assert!(!is_conflict(1, base, base, &confirmed));
+        assert!(is_conflict(1, base, "2024-05-01T11:00:00Z", &confirmed));
+
+        // an earlier change in the same replay moved updated_at on
+        confirmed.insert(1, "2024-05-01T11:00:00Z".to_string());
+        assert!(!is_conflict(1, base, "2024-05-01T11:00:00Z", &confirmed));
+        // ...but a later edit elsewhere still counts
+        assert!(is_conflict(1, base, "2024-05-01T12:00:00Z", &confirmed));
+        assert!(is_conflict(2, base, "2024-05-01T11:00:00Z", &confirmed));
+    }
+
+    #[test]
+    fn offline_todos_get_their_server_ids() {
+        let report = SyncReport {
+            id_map: vec![(-1, 42)],
+            ..SyncReport::default()
+        };
+        assert_eq!(resolve(update(-1, "Renamed"), &report), Some(update(42, "Renamed")));
+        assert_eq!(resolve(delete(-1), &report), Some(delete(42)));
+        assert_eq!(resolve(update(7, "Saved"), &report), Some(update(7, "Saved")));
+
+        // the create of -2 failed, so its later changes have nothing to apply to
+        assert_eq!(resolve(update(-2, "Lost"), &report), None);
+        let create = PendingChange::Create {
+            local_id: -2,
+            todo: Todo::default(),
+        };
+        assert_eq!(resolve(create.clone(), &report), Some(create));
+    }
+
+    #[test]
+    fn online_creates_are_unsaved_until_they_get_an_id() {
+        let queue = SyncQueue {
+            changes: vec![PendingChange::Create {
+                local_id: -1,
+                todo: Todo::default(),
+            }],
+            ..SyncQueue::default()
+        };
+        // -1 was created offline and its changes are queued behind the create
+        assert!(!queue.is_unsaved(-1));
+        assert!(queue.is_unsaved(-2));
+        assert!(!queue.is_unsaved(3));
+    }
+
+    #[test]
+    fn leftovers_go_back_ahead_of_new_changes() {
+        let report = SyncReport {
+            remaining: vec![update(-1, "First"), delete(5)],
+            id_map: vec![(-1, 42)],
+            ..SyncReport::default()
+        };
+        let queue = requeue(&report, vec![update(-1, "Meanwhile"), delete(6)]);
+        assert_eq!(
+            queue,
+            vec![update(42, "First"), delete(5), update(42, "Meanwhile"), delete(6)]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::api::batch::BulkAction;
+use crate::api::models::{ListResponse, Priority};
+use crate::context::auth::use_auth;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct BulkBarProps {
+    /// Number of selected todos.
+    pub selected: usize,
+    /// Number of todos currently visible, all of which "Select all" picks.
+    pub visible: usize,
+    pub onselectall: Callback<()>,
+    pub onclear: Callback<()>,
+    pub onaction: Callback<BulkAction>,
+    /// List shown by the screen, left out of the "Move to" choices.
+    #[prop_or_default]
+    pub current_list: Option<i64>,
+}
+
+/// Toolbar of batch actions shown while todos are selected.
+#[function_component(BulkBar)]
+pub fn bulk_bar(props: &BulkBarProps) -> Html {
+    let auth = use_auth();
+    let lists = use_state(Vec::<ListResponse>::new);
+    let tag = use_state(String::new);
+
+    {
+        let lists = lists.clone();
+        let client = auth.api_client();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                // the sidebar reports list errors; here the menu just stays short
+                if let Ok(result) = client.list_lists().await {
+                    lists.set(result);
+                }
+            });
+            || ()
+        });
+    }
+
+    let emit = |action: BulkAction| {
+        let onaction = props.onaction.clone();
+        Callback::from(move |_: MouseEvent| onaction.emit(action.clone()))
+    };
+
+    let on_move = {
+        let onaction = props.onaction.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            select.set_value("");
+            match value.as_str() {
+                "" => {}
+                "none" => onaction.emit(BulkAction::MoveToList(None)),
+                id => {
+                    if let Ok(id) = id.parse() {
+                        onaction.emit(BulkAction::MoveToList(Some(id)));
+                    }
+                }
+            }
+        })
+    };
+
+    let on_priority = {
+        let onaction = props.onaction.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            select.set_value("");
+            if !value.is_empty() {
+                onaction.emit(BulkAction::SetPriority(Priority::from_str(&value)));
+            }
+        })
+    };
+
+    let on_tag_input = {
+        let tag = tag.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            tag.set(input.value());
+        })
+    };
+
+    let on_tag_submit = {
+        let tag = tag.clone();
+        let onaction = props.onaction.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let value = tag.trim().trim_start_matches('#').trim().to_string();
+            if !value.is_empty() {
+                onaction.emit(BulkAction::AddTag(value));
+                tag.set(String::new());
+            }
+        })
+    };
+
+    let on_select_all = {
+        let onselectall = props.onselectall.clone();
+        Callback::from(move |_| onselectall.emit(()))
+    };
+    let on_clear = {
+        let onclear = props.onclear.clone();
+        Callback::from(move |_| onclear.emit(()))
+    };
+
+    let button_class = "px-3 py-1 text-sm text-gray-200 bg-gray-700 rounded hover:bg-gray-600";
+    let select_class = "px-2 py-1 text-sm text-gray-200 bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-1 focus:ring-blue-500";
+
+    html! {
+        <div class="sticky top-0 z-40 flex flex-wrap items-center gap-2 p-3 mb-4 bg-gray-800 border border-blue-700 rounded-lg shadow-lg">
+            <span class="mr-2 text-sm font-semibold text-white">{format!("{} selected", props.selected)}</span>
+            if props.selected < props.visible {
+                <button onclick={on_select_all} class="text-sm text-blue-400 hover:underline">
+                    {format!("Select all {}", props.visible)}
+                </button>
+            }
+            <button onclick={on_clear} class="mr-auto text-sm text-gray-400 hover:text-white">{"Clear"}</button>
+
+            <button onclick={emit(BulkAction::Complete)} class={button_class}>{"✓ Complete"}</button>
+            <button onclick={emit(BulkAction::Reopen)} class={button_class}>{"↺ Reopen"}</button>
+            <select onchange={on_move} class={select_class}>
+                <option value="" selected=true>{"Move to…"}</option>
+                if props.current_list.is_some() {
+                    <option value="none">{"No list"}</option>
+                }
+                { for lists.iter().filter(|list| !list.archived && Some(list.id) != props.current_list).map(|list| html! {
+                    <option value={list.id.to_string()}>{&list.name}</option>
+                }) }
+            </select>
+            <select onchange={on_priority} class={select_class}>
+                <option value="" selected=true>{"Priority…"}</option>
+                { for Priority::ALL.iter().map(|priority| html! {
+                    <option value={priority.as_str()}>{priority.label()}</option>
+                }) }
+            </select>
+            <form onsubmit={on_tag_submit} class="flex gap-1">
+                <input
+                    type="text"
+                    placeholder="Add tag"
+                    value={(*tag).clone()}
+                    oninput={on_tag_input}
+                    class={classes!(select_class, "w-28")}
+                />
+                <button type="submit" disabled={tag.trim().is_empty()} class={classes!(button_class, "disabled:opacity-50")}>{"#"}</button>
+            </form>
+            <button onclick={emit(BulkAction::Delete)} class="px-3 py-1 text-sm text-red-200 rounded bg-red-900 hover:bg-red-800">
+                {"🗑 Delete"}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,145 @@
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+use crate::api::models::ChecklistItem;
+
+/// Changes the checklist in place.
+type ItemsEdit = Box<dyn Fn(&mut Vec<ChecklistItem>)>;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ChecklistProps {
+    pub items: Vec<ChecklistItem>,
+    /// Receives the whole list after every add, toggle, move or delete.
+    pub onchange: Callback<Vec<ChecklistItem>>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Inline checklist editor shown inside a todo card.
+#[function_component(Checklist)]
+pub fn checklist(props: &ChecklistProps) -> Html {
+    let new_title = use_state(String::new);
+
+    let on_input = {
+        let new_title = new_title.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            new_title.set(input.value());
+        })
+    };
+
+    let add_item = {
+        let new_title = new_title.clone();
+        let items = props.items.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |_: ()| {
+            let title = new_title.trim().to_string();
+            if title.is_empty() {
+                return;
+            }
+            let id = items.iter().map(|item| item.id).max().map_or(1, |id| id + 1);
+            let mut items = items.clone();
+            items.push(ChecklistItem {
+                id,
+                title,
+                done: false,
+            });
+            onchange.emit(items);
+            new_title.set(String::new());
+        })
+    };
+
+    let on_keydown = {
+        let add_item = add_item.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                e.prevent_default();
+                add_item.emit(());
+            }
+        })
+    };
+
+    // emits a copy of the list changed by `edit`
+    let update = |edit: ItemsEdit| {
+        let items = props.items.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut items = items.clone();
+            edit(&mut items);
+            onchange.emit(items);
+        })
+    };
+
+    let last = props.items.len().saturating_sub(1);
+
+    html! {
+        <div class="mt-3 space-y-1">
+            { for props.items.iter().enumerate().map(|(index, item)| {
+                let toggle = update(Box::new(move |items| items[index].done = !items[index].done));
+                let move_up = update(Box::new(move |items| items.swap(index, index - 1)));
+                let move_down = update(Box::new(move |items| items.swap(index, index + 1)));
+                let remove = update(Box::new(move |items| {
+                    items.remove(index);
+                }));
+                html! {
+                    <div key={item.id} class="flex items-center gap-2 text-sm group">
+                        <input
+                            type="checkbox"
+                            checked={item.done}
+                            onclick={toggle}
+                            disabled={props.disabled}
+                            class="accent-green-500"
+                        />
+                        <span class={classes!("flex-1", if item.done { "line-through text-gray-500" } else { "text-gray-200" })}>
+                            {&item.title}
+                        </span>
+                        <div class="flex gap-1 opacity-0 group-hover:opacity-100">
+                            <button
+                                onclick={move_up}
+                                disabled={props.disabled || index == 0}
+                                class="px-1 text-gray-400 hover:text-white disabled:opacity-30"
+                                title="Move up"
+                            >
+                                {"↑"}
+                            </button>
+                            <button
+                                onclick={move_down}
+                                disabled={props.disabled || index == last}
+                                class="px-1 text-gray-400 hover:text-white disabled:opacity-30"
+                                title="Move down"
+                            >
+                                {"↓"}
+                            </button>
+                            <button
+                                onclick={remove}
+                                disabled={props.disabled}
+                                class="px-1 text-red-400 hover:text-red-300"
+                                title="Delete item"
+                            >
+                                {"✕"}
+                            </button>
+                        </div>
+                    </div>
+                }
+            }) }
+            <div class="flex gap-2 pt-1">
+                <input
+                    type="text"
+                    placeholder="Add an item..."
+                    value={(*new_title).clone()}
+                    oninput={on_input}
+                    onkeydown={on_keydown}
+                    disabled={props.disabled}
+                    class="flex-1 px-2 py-1 text-sm text-white placeholder-gray-500 bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-1 focus:ring-blue-500"
+                />
+                <button
+                    onclick={move |_| add_item.emit(())}
+                    disabled={props.disabled || new_title.trim().is_empty()}
+                    class="px-2 py-1 text-sm text-gray-200 bg-gray-600 rounded hover:bg-gray-500 disabled:opacity-50"
+                >
+                    {"Add"}
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,168 @@
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+use crate::hooks::use_shortcuts::Shortcut;
+use crate::utils::fuzzy::fuzzy_filter;
+
+/// Most results listed at once; typing narrows the rest down.
+const MAX_RESULTS: usize = 50;
+
+/// An entry of the command palette.
+#[derive(Clone, PartialEq)]
+pub struct Command {
+    pub label: AttrValue,
+    /// Shown on the right, e.g. the shortcut key or "Todo".
+    pub hint: AttrValue,
+    pub run: Callback<()>,
+}
+
+impl From<&Shortcut> for Command {
+    fn from(shortcut: &Shortcut) -> Self {
+        Command {
+            label: AttrValue::from(shortcut.label),
+            hint: AttrValue::from(shortcut.hint()),
+            run: shortcut.run.clone(),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct CommandPaletteProps {
+    pub commands: Vec<Command>,
+    pub onclose: Callback<()>,
+}
+
+/// Searchable list of commands, picked with ↑/↓ and Enter.
+#[function_component(CommandPalette)]
+pub fn command_palette(props: &CommandPaletteProps) -> Html {
+    let query = use_state(String::new);
+    let highlighted = use_state(|| 0usize);
+    let input_ref = use_node_ref();
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+            || ()
+        });
+    }
+
+    let matches: Vec<Command> = fuzzy_filter(&query, &props.commands, |command| command.label.as_str())
+        .into_iter()
+        .take(MAX_RESULTS)
+        .cloned()
+        .collect();
+    let active = (*highlighted).min(matches.len().saturating_sub(1));
+
+    // closes the palette first so the command sees the screen without it
+    let run = {
+        let onclose = props.onclose.clone();
+        move |command: &Command| {
+            let onclose = onclose.clone();
+            let command = command.run.clone();
+            move || {
+                onclose.emit(());
+                command.emit(());
+            }
+        }
+    };
+
+    let on_input = {
+        let query = query.clone();
+        let highlighted = highlighted.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+            highlighted.set(0);
+        })
+    };
+
+    let on_keydown = {
+        let highlighted = highlighted.clone();
+        let onclose = props.onclose.clone();
+        let matches = matches.clone();
+        let run = run.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" => {
+                e.prevent_default();
+                if active + 1 < matches.len() {
+                    highlighted.set(active + 1);
+                }
+            }
+            "ArrowUp" => {
+                e.prevent_default();
+                highlighted.set(active.saturating_sub(1));
+            }
+            "Enter" => {
+                e.prevent_default();
+                if let Some(command) = matches.get(active) {
+                    run(command)();
+                }
+            }
+            "Escape" => {
+                e.prevent_default();
+                onclose.emit(());
+            }
+            _ => {}
+        })
+    };
+
+    let on_backdrop = {
+        let onclose = props.onclose.clone();
+        Callback::from(move |_| onclose.emit(()))
+    };
+
+    html! {
+        <div
+            class="fixed inset-0 z-50 flex items-start justify-center pt-24 bg-black/60"
+            onclick={on_backdrop}
+        >
+            <div
+                role="dialog"
+                aria-modal="true"
+                class="w-full max-w-lg overflow-hidden bg-gray-800 border border-gray-700 rounded-lg shadow-xl"
+                onclick={|e: MouseEvent| e.stop_propagation()}
+            >
+                <input
+                    ref={input_ref}
+                    type="text"
+                    placeholder="Type a command or a todo…"
+                    value={(*query).clone()}
+                    oninput={on_input}
+                    onkeydown={on_keydown}
+                    class="w-full px-4 py-3 text-white placeholder-gray-400 bg-gray-800 border-b border-gray-700 focus:outline-none"
+                />
+                <ul class="overflow-y-auto max-h-80">
+                    if matches.is_empty() {
+                        <li class="px-4 py-3 text-sm text-gray-400">{"No matches"}</li>
+                    }
+                    { for matches.iter().enumerate().map(|(index, command)| {
+                        let onclick = {
+                            let run = run(command);
+                            Callback::from(move |_| run())
+                        };
+                        let onmouseenter = {
+                            let highlighted = highlighted.clone();
+                            Callback::from(move |_| highlighted.set(index))
+                        };
+                        html! {
+                            <li
+                                {onclick}
+                                {onmouseenter}
+                                class={classes!(
+                                    "flex", "items-center", "justify-between", "gap-4", "px-4", "py-2", "text-sm", "cursor-pointer",
+                                    if index == active { "bg-blue-600 text-white" } else { "text-gray-200" }
+                                )}
+                            >
+                                <span class="truncate">{&command.label}</span>
+                                <span class="shrink-0 text-xs text-gray-400">{&command.hint}</span>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,80 @@
+use web_sys::KeyboardEvent;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ConfirmDialogProps {
+    pub title: AttrValue,
+    pub message: AttrValue,
+    #[prop_or(AttrValue::from("Confirm"))]
+    pub confirm_label: AttrValue,
+    pub onconfirm: Callback<()>,
+    pub oncancel: Callback<()>,
+}
+
+/// Modal asking to confirm a destructive action; Escape or a click outside cancels.
+#[function_component(ConfirmDialog)]
+pub fn confirm_dialog(props: &ConfirmDialogProps) -> Html {
+    let confirm_ref = use_node_ref();
+
+    {
+        // focus the confirm button so Enter confirms and Escape reaches the dialog
+        let confirm_ref = confirm_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(button) = confirm_ref.cast::<web_sys::HtmlElement>() {
+                let _ = button.focus();
+            }
+            || ()
+        });
+    }
+
+    let on_confirm = {
+        let onconfirm = props.onconfirm.clone();
+        Callback::from(move |_| onconfirm.emit(()))
+    };
+    let on_cancel = {
+        let oncancel = props.oncancel.clone();
+        Callback::from(move |_| oncancel.emit(()))
+    };
+    let on_keydown = {
+        let oncancel = props.oncancel.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                e.prevent_default();
+                oncancel.emit(());
+            }
+        })
+    };
+
+    html! {
+        <div
+            class="fixed inset-0 z-50 flex items-center justify-center bg-black/60"
+            onclick={on_cancel.clone()}
+            onkeydown={on_keydown}
+        >
+            <div
+                role="alertdialog"
+                aria-modal="true"
+                class="w-full max-w-sm p-6 bg-gray-800 border border-gray-700 rounded-lg shadow-xl"
+                onclick={|e: MouseEvent| e.stop_propagation()}
+            >
+                <h2 class="mb-2 text-lg font-semibold text-white">{&props.title}</h2>
+                <p class="mb-6 text-sm text-gray-300">{&props.message}</p>
+                <div class="flex justify-end gap-2">
+                    <button
+                        onclick={on_cancel}
+                        class="px-4 py-2 text-sm text-gray-300 rounded hover:bg-gray-700"
+                    >
+                        {"Cancel"}
+                    </button>
+                    <button
+                        ref={confirm_ref}
+                        onclick={on_confirm}
+                        class="px-4 py-2 text-sm font-semibold text-white bg-red-600 rounded hover:bg-red-700 focus:outline-none focus:ring-2 focus:ring-red-400"
+                    >
+                        {&props.confirm_label}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,319 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::hooks::use_navigator;
+
+use crate::api::error::ApiError;
+use crate::api::models::{ListResponse, NewList, UpdateListType};
+use crate::context::auth::use_auth;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ListSidebarProps {
+    /// List shown by the todo screen; `None` for all todos.
+    #[prop_or_default]
+    pub current: Option<i64>,
+    /// Bumped by the screen when lists were created elsewhere, e.g. by an import.
+    #[prop_or_default]
+    pub refresh: u32,
+    pub onerror: Callback<(&'static str, ApiError)>,
+}
+
+/// Sidebar of named todo lists: switch, create, rename, archive and delete.
+#[function_component(ListSidebar)]
+pub fn list_sidebar(props: &ListSidebarProps) -> Html {
+    let auth = use_auth();
+    let client = auth.api_client();
+    let navigator = use_navigator();
+    let lists = use_state(Vec::<ListResponse>::new);
+    let new_name = use_state(String::new);
+    let renaming = use_state(|| Option::<(i64, String)>::None);
+    let show_archived = use_state(|| false);
+
+    let fetch_lists = {
+        let lists = lists.clone();
+        let client = client.clone();
+        let onerror = props.onerror.clone();
+        Callback::from(move |_| {
+            let lists = lists.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            spawn_local(async move {
+                match client.list_lists().await {
+                    Ok(result) => lists.set(result),
+                    // the todo screen already reports an unreachable backend
+                    Err(ApiError::Network(_)) => {}
+                    Err(err) => onerror.emit(("Could not load lists.", err)),
+                }
+            });
+        })
+    };
+
+    {
+        let fetch_lists = fetch_lists.clone();
+        use_effect_with((props.current, props.refresh), move |_| {
+            fetch_lists.emit(());
+            || ()
+        });
+    }
+
+    let open_list = {
+        let navigator = navigator.clone();
+        Callback::from(move |id: Option<i64>| {
+            if let Some(navigator) = &navigator {
+                match id {
+                    Some(id) => navigator.push(&crate::Route::List { id }),
+                    None => navigator.push(&crate::Route::Todo),
+                }
+            }
+        })
+    };
+
+    let on_new_name = {
+        let new_name = new_name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            new_name.set(input.value());
+        })
+    };
+
+    let on_create = {
+        let new_name = new_name.clone();
+        let client = client.clone();
+        let fetch_lists = fetch_lists.clone();
+        let open_list = open_list.clone();
+        let onerror = props.onerror.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let name = new_name.trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let new_name = new_name.clone();
+            let client = client.clone();
+            let fetch_lists = fetch_lists.clone();
+            let open_list = open_list.clone();
+            let onerror = onerror.clone();
+            spawn_local(async move {
+                match client.create_list(&NewList { name }).await {
+                    Ok(created) => {
+                        new_name.set(String::new());
+                        fetch_lists.emit(());
+                        if let Some(list) = created {
+                            open_list.emit(Some(list.id));
+                        }
+                    }
+                    Err(err) => onerror.emit(("Could not create list.", err)),
+                }
+            });
+        })
+    };
+
+    let send_update = {
+        let client = client.clone();
+        let fetch_lists = fetch_lists.clone();
+        let onerror = props.onerror.clone();
+        Callback::from(move |update: UpdateListType| {
+            let client = client.clone();
+            let fetch_lists = fetch_lists.clone();
+            let onerror = onerror.clone();
+            spawn_local(async move {
+                if let Err(err) = client.update_list(&update).await {
+                    onerror.emit(("Could not update list.", err));
+                }
+                fetch_lists.emit(());
+            });
+        })
+    };
+
+    let on_rename_input = {
+        let renaming = renaming.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some((id, _)) = *renaming {
+                renaming.set(Some((id, input.value())));
+            }
+        })
+    };
+
+    let on_rename_submit = {
+        let renaming = renaming.clone();
+        let send_update = send_update.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some((id, name)) = (*renaming).clone() {
+                let name = name.trim().to_string();
+                if !name.is_empty() {
+                    send_update.emit(UpdateListType {
+                        id,
+                        name: Some(name),
+                        archived: None,
+                    });
+                }
+            }
+            renaming.set(None);
+        })
+    };
+
+    let on_delete = {
+        let client = client.clone();
+        let fetch_lists = fetch_lists.clone();
+        let open_list = open_list.clone();
+        let current = props.current;
+        let onerror = props.onerror.clone();
+        Callback::from(move |list: ListResponse| {
+            let confirmed = web_sys::window()
+                .and_then(|window| {
+                    window
+                        .confirm_with_message(&format!("Delete the list \"{}\"?", list.name))
+                        .ok()
+                })
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let client = client.clone();
+            let fetch_lists = fetch_lists.clone();
+            let open_list = open_list.clone();
+            let onerror = onerror.clone();
+            spawn_local(async move {
+                match client.delete_list(list.id).await {
+                    Ok(()) => {
+                        fetch_lists.emit(());
+                        if current == Some(list.id) {
+                            open_list.emit(None);
+                        }
+                    }
+                    Err(err) => onerror.emit(("Could not delete list.", err)),
+                }
+            });
+        })
+    };
+
+    let toggle_archived = {
+        let show_archived = show_archived.clone();
+        Callback::from(move |_| show_archived.set(!*show_archived))
+    };
+
+    let render_list = |list: &ListResponse| {
+        let is_current = props.current == Some(list.id);
+        let open = {
+            let open_list = open_list.clone();
+            let id = list.id;
+            Callback::from(move |_| open_list.emit(Some(id)))
+        };
+        let rename = {
+            let renaming = renaming.clone();
+            let list = list.clone();
+            Callback::from(move |_| renaming.set(Some((list.id, list.name.clone()))))
+        };
+        let archive = {
+            let send_update = send_update.clone();
+            let list = list.clone();
+            Callback::from(move |_| {
+                send_update.emit(UpdateListType {
+                    id: list.id,
+                    name: None,
+                    archived: Some(!list.archived),
+                })
+            })
+        };
+        let delete = {
+            let on_delete = on_delete.clone();
+            let list = list.clone();
+            Callback::from(move |_| on_delete.emit(list.clone()))
+        };
+
+        html! {
+            <li key={list.id} class="group">
+                if let Some((_, name)) = renaming.as_ref().filter(|(id, _)| *id == list.id) {
+                    <form onsubmit={on_rename_submit.clone()}>
+                        <input
+                            type="text"
+                            value={name.clone()}
+                            oninput={on_rename_input.clone()}
+                            class="w-full px-2 py-1 text-sm text-white bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-1 focus:ring-blue-500"
+                        />
+                    </form>
+                } else {
+                    <div class={classes!(
+                        "flex", "items-center", "gap-2", "px-2", "py-1", "rounded", "cursor-pointer",
+                        if is_current { "bg-gray-700 text-white" } else { "text-gray-300 hover:bg-gray-800" }
+                    )}>
+                        <span onclick={open} class="flex-1 truncate">{&list.name}</span>
+                        if let Some(pending) = list.pending {
+                            <span class="text-xs text-gray-500" title="Pending / total">
+                                {format!("{}/{}", pending, pending + list.completed.unwrap_or(0))}
+                            </span>
+                        }
+                        <div class="hidden gap-1 text-xs group-hover:flex">
+                            <button onclick={rename} class="text-gray-400 hover:text-white" title="Rename">{"✎"}</button>
+                            <button onclick={archive} class="text-gray-400 hover:text-white" title={if list.archived { "Unarchive" } else { "Archive" }}>
+                                {if list.archived { "↺" } else { "🗄" }}
+                            </button>
+                            <button onclick={delete} class="text-red-400 hover:text-red-300" title="Delete">{"✕"}</button>
+                        </div>
+                    </div>
+                }
+            </li>
+        }
+    };
+
+    let open_all = {
+        let open_list = open_list.clone();
+        Callback::from(move |_| open_list.emit(None))
+    };
+    let archived_count = lists.iter().filter(|list| list.archived).count();
+
+    html! {
+        <aside class="w-56 shrink-0">
+            <div class="p-4 space-y-4 bg-gray-800 rounded-lg shadow-lg">
+                <button
+                    onclick={open_all}
+                    class={classes!(
+                        "w-full", "px-2", "py-1", "text-left", "rounded",
+                        if props.current.is_none() { "bg-gray-700 text-white" } else { "text-gray-300 hover:bg-gray-700" }
+                    )}
+                >
+                    {"All todos"}
+                </button>
+
+                <div>
+                    <h2 class="mb-2 text-xs font-semibold tracking-wide text-gray-500 uppercase">{"Lists"}</h2>
+                    <ul class="space-y-1">
+                        { for lists.iter().filter(|list| !list.archived).map(render_list) }
+                    </ul>
+                </div>
+
+                <form onsubmit={on_create} class="flex gap-2">
+                    <input
+                        type="text"
+                        placeholder="New list"
+                        value={(*new_name).clone()}
+                        oninput={on_new_name}
+                        class="flex-1 min-w-0 px-2 py-1 text-sm text-white placeholder-gray-500 bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-1 focus:ring-blue-500"
+                    />
+                    <button
+                        type="submit"
+                        disabled={new_name.trim().is_empty()}
+                        class="px-2 py-1 text-sm text-white bg-blue-600 rounded hover:bg-blue-700 disabled:opacity-50"
+                    >
+                        {"+"}
+                    </button>
+                </form>
+
+                if archived_count > 0 {
+                    <div>
+                        <button onclick={toggle_archived} class="text-xs text-gray-500 hover:text-gray-300">
+                            {format!("{} Archived ({})", if *show_archived { "▾" } else { "▸" }, archived_count)}
+                        </button>
+                        if *show_archived {
+                            <ul class="mt-2 space-y-1 opacity-75">
+                                { for lists.iter().filter(|list| list.archived).map(render_list) }
+                            </ul>
+                        }
+                    </div>
+                }
+            </div>
+        </aside>
+    }
+}
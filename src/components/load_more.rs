@@ -0,0 +1,72 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct LoadMoreProps {
+    pub onload: Callback<()>,
+    /// Stops observing, e.g. while a page is in flight.
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Sentinel placed after a paged list: emits `onload` when it scrolls into
+/// view, with a button as fallback.
+#[function_component(LoadMore)]
+pub fn load_more(props: &LoadMoreProps) -> Html {
+    let node = use_node_ref();
+
+    {
+        let node = node.clone();
+        use_effect_with(
+            (props.onload.clone(), props.disabled),
+            move |(onload, disabled)| {
+                let observer = if *disabled {
+                    None
+                } else {
+                    let onload = onload.clone();
+                    let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+                        let visible = entries.iter().any(|entry| {
+                            entry
+                                .dyn_into::<IntersectionObserverEntry>()
+                                .is_ok_and(|entry| entry.is_intersecting())
+                        });
+                        if visible {
+                            onload.emit(());
+                        }
+                    });
+                    node.cast::<Element>().and_then(|element| {
+                        let observer =
+                            IntersectionObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+                        observer.observe(&element);
+                        Some((observer, callback))
+                    })
+                };
+                move || {
+                    if let Some((observer, _callback)) = observer {
+                        observer.disconnect();
+                    }
+                }
+            },
+        );
+    }
+
+    let onclick = {
+        let onload = props.onload.clone();
+        Callback::from(move |_| onload.emit(()))
+    };
+
+    html! {
+        <div ref={node} class="py-4 text-center">
+            <button
+                {onclick}
+                disabled={props.disabled}
+                class="px-4 py-2 text-sm text-gray-300 bg-gray-800 rounded-lg hover:bg-gray-700 disabled:opacity-50"
+            >
+                if props.disabled { {"Loading..."} } else { {"Load more"} }
+            </button>
+        </div>
+    }
+}
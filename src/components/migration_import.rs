@@ -0,0 +1,432 @@
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+use crate::api::batch::{create_all, CREATE_CHUNK_SIZE};
+use crate::api::client::TodoApiClient;
+use crate::api::error::ApiError;
+use crate::api::models::{find_list, ListResponse, NewList, Todo};
+use crate::context::auth::use_auth;
+use crate::utils::migration::{plan_migration, MigrationPlan};
+
+/// Creates the list named `name` and returns it.
+async fn create_named_list(client: &TodoApiClient, name: &str) -> Result<ListResponse, ApiError> {
+    if let Some(list) = client.create_list(&NewList { name: name.to_string() }).await? {
+        return Ok(list);
+    }
+    // the backend did not echo the list, so its id is looked up by name
+    let lists = client.list_lists().await?;
+    find_list(&lists, name)
+        .cloned()
+        .ok_or_else(|| ApiError::Decode(format!("list \"{}\" was not returned after creating it", name)))
+}
+
+/// A todo that could not be created, and why.
+#[derive(Clone, PartialEq)]
+struct Failure {
+    title: String,
+    error: String,
+}
+
+#[derive(Clone, PartialEq)]
+enum Stage {
+    /// Dry run: nothing has been sent yet.
+    Review {
+        file_name: String,
+        plan: MigrationPlan,
+        lists: Vec<ListResponse>,
+    },
+    Running {
+        done: usize,
+        total: usize,
+        failures: Vec<Failure>,
+    },
+    Finished {
+        created: usize,
+        total: usize,
+        failures: Vec<Failure>,
+    },
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct MigrationImportProps {
+    /// Importing needs the backend; the button is disabled while offline.
+    pub offline: bool,
+    /// Called once todos were created, so the screen can reload todos and lists.
+    pub oncomplete: Callback<()>,
+    /// Files that are not a supported export.
+    pub onerror: Callback<String>,
+    pub onapierror: Callback<(&'static str, ApiError)>,
+}
+
+/// Import of Todoist, Trello and Microsoft To Do exports: a dry-run summary,
+/// then lists and todos created with progress and per-todo errors.
+#[function_component(MigrationImport)]
+pub fn migration_import(props: &MigrationImportProps) -> Html {
+    let auth = use_auth();
+    let client = auth.api_client();
+    let stage = use_state(|| Option::<Stage>::None);
+    let start_ref = use_node_ref();
+
+    {
+        // focus the start button so Enter imports and Escape reaches the dialog
+        let start_ref = start_ref.clone();
+        let reviewing = matches!(*stage, Some(Stage::Review { .. }));
+        use_effect_with(reviewing, move |_| {
+            if let Some(button) = start_ref.cast::<web_sys::HtmlElement>() {
+                let _ = button.focus();
+            }
+            || ()
+        });
+    }
+
+    let on_file = {
+        let stage = stage.clone();
+        let client = client.clone();
+        let onerror = props.onerror.clone();
+        let onapierror = props.onapierror.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // picking the same file again should fire another change
+            input.set_value("");
+            let stage = stage.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            let onapierror = onapierror.clone();
+            spawn_local(async move {
+                let Some(text) = JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string()) else {
+                    onerror.emit(format!("Could not read {}.", file.name()));
+                    return;
+                };
+                let plan = match plan_migration(&text) {
+                    Ok(plan) => plan,
+                    Err(err) => {
+                        onerror.emit(format!("Could not import {}. {}", file.name(), err));
+                        return;
+                    }
+                };
+                // only tells new lists from existing ones; the import looks them up again
+                let lists = match client.list_lists().await {
+                    Ok(lists) => lists,
+                    Err(err @ ApiError::Unauthorized(_)) => {
+                        onapierror.emit(("Could not load lists.", err));
+                        return;
+                    }
+                    Err(_) => Vec::new(),
+                };
+                stage.set(Some(Stage::Review {
+                    file_name: file.name(),
+                    plan,
+                    lists,
+                }));
+            });
+        })
+    };
+
+    let on_start = {
+        let stage = stage.clone();
+        let client = client.clone();
+        let oncomplete = props.oncomplete.clone();
+        let onapierror = props.onapierror.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(Stage::Review { plan, .. }) = (*stage).clone() else {
+                return;
+            };
+            let total = plan.todo_count();
+            stage.set(Some(Stage::Running {
+                done: 0,
+                total,
+                failures: Vec::new(),
+            }));
+
+            let stage = stage.clone();
+            let client = client.clone();
+            let oncomplete = oncomplete.clone();
+            let onapierror = onapierror.clone();
+            spawn_local(async move {
+                let mut lists = client.list_lists().await.unwrap_or_default();
+                let mut done = 0;
+                let mut created = 0;
+                let mut failures: Vec<Failure> = Vec::new();
+                let mut unauthorized = None;
+
+                'lists: for planned in plan.lists {
+                    let list_id = match &planned.name {
+                        None => Ok(None),
+                        Some(name) => match find_list(&lists, name) {
+                            Some(list) => Ok(Some(list.id)),
+                            None => create_named_list(&client, name).await.map(|list| {
+                                let id = list.id;
+                                lists.push(list);
+                                Some(id)
+                            }),
+                        },
+                    };
+                    let list_id = match list_id {
+                        Ok(list_id) => list_id,
+                        Err(err @ ApiError::Unauthorized(_)) => {
+                            unauthorized = Some(err);
+                            break;
+                        }
+                        Err(err) => {
+                            let name = planned.name.unwrap_or_default();
+                            let error = format!("Could not create list \"{}\": {}", name, err);
+                            failures.extend(planned.todos.iter().map(|todo| Failure {
+                                title: todo.title.clone(),
+                                error: error.clone(),
+                            }));
+                            done += planned.todos.len();
+                            stage.set(Some(Stage::Running {
+                                done,
+                                total,
+                                failures: failures.clone(),
+                            }));
+                            continue;
+                        }
+                    };
+
+                    let todos: Vec<Todo> = planned
+                        .todos
+                        .into_iter()
+                        .map(|todo| Todo { list_id, ..todo })
+                        .collect();
+                    // one chunk per call, so progress moves while the backend works
+                    for chunk in todos.chunks(CREATE_CHUNK_SIZE) {
+                        let results = create_all(&client, chunk).await;
+                        for (todo, result) in chunk.iter().zip(results) {
+                            match result {
+                                Ok(_) => created += 1,
+                                Err(err @ ApiError::Unauthorized(_)) => unauthorized = Some(err),
+                                Err(err) => failures.push(Failure {
+                                    title: todo.title.clone(),
+                                    error: err.to_string(),
+                                }),
+                            }
+                        }
+                        done += chunk.len();
+                        if unauthorized.is_some() {
+                            break 'lists;
+                        }
+                        stage.set(Some(Stage::Running {
+                            done,
+                            total,
+                            failures: failures.clone(),
+                        }));
+                    }
+                }
+
+                if created > 0 {
+                    oncomplete.emit(());
+                }
+                match unauthorized {
+                    Some(err) => {
+                        stage.set(None);
+                        onapierror.emit(("Could not import todos.", err));
+                    }
+                    None => stage.set(Some(Stage::Finished {
+                        created,
+                        total,
+                        failures,
+                    })),
+                }
+            });
+        })
+    };
+
+    let on_close = {
+        let stage = stage.clone();
+        Callback::from(move |_| {
+            // the import keeps going in the background otherwise, unseen
+            if !matches!(*stage, Some(Stage::Running { .. })) {
+                stage.set(None);
+            }
+        })
+    };
+
+    let on_keydown = {
+        let on_close = on_close.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                e.prevent_default();
+                on_close.emit(());
+            }
+        })
+    };
+
+    let control_class = "px-2 py-1 text-xs text-gray-300 bg-gray-800 border border-gray-600 rounded hover:text-white";
+    let summary_row = |label: &str, value: String| {
+        html! {
+            <div class="flex justify-between py-1">
+                <dt class="text-gray-400">{label.to_string()}</dt>
+                <dd class="text-gray-200">{value}</dd>
+            </div>
+        }
+    };
+    let failure_list = |failures: &[Failure]| {
+        html! {
+            if !failures.is_empty() {
+                <ul class="flex-1 mb-4 overflow-y-auto text-sm divide-y divide-gray-700">
+                    { for failures.iter().map(|failure| html! {
+                        <li class="py-2">
+                            <span class="text-gray-200">{&failure.title}</span>
+                            <span class="block text-xs text-red-400">{&failure.error}</span>
+                        </li>
+                    }) }
+                </ul>
+            }
+        }
+    };
+
+    let dialog = match &*stage {
+        None => html! {},
+        Some(Stage::Review { file_name, plan, lists }) => {
+            let tags = plan.tags();
+            html! {
+                <>
+                    <h2 class="mb-1 text-lg font-semibold text-white">
+                        {format!("Import {} todo(s) from {}?", plan.todo_count(), plan.source.label())}
+                    </h2>
+                    <p class="mb-4 text-sm text-gray-400">
+                        {format!("From {}. Nothing has been created yet.", file_name)}
+                    </p>
+                    <ul class="mb-4 overflow-y-auto text-sm divide-y divide-gray-700">
+                        { for plan.lists.iter().map(|list| {
+                            let (name, badge) = match &list.name {
+                                None => ("No list".to_string(), None),
+                                Some(name) if find_list(lists, name).is_some() => {
+                                    (name.clone(), Some(("existing list", "text-gray-400")))
+                                }
+                                Some(name) => (name.clone(), Some(("new list", "text-blue-300"))),
+                            };
+                            html! {
+                                <li class="flex items-center justify-between gap-2 py-2">
+                                    <span class="text-gray-200 truncate">
+                                        {format!("📁 {}", name)}
+                                        if let Some((badge, class)) = badge {
+                                            <span class={classes!("ml-2", "text-xs", class)}>{badge}</span>
+                                        }
+                                    </span>
+                                    <span class="text-gray-400">{format!("{} todo(s)", list.todos.len())}</span>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                    <dl class="mb-4 text-sm">
+                        {summary_row("Completed", plan.completed_count().to_string())}
+                        {summary_row("With a due date", plan.due_count().to_string())}
+                        {summary_row("Checklist items", plan.checklist_count().to_string())}
+                        {summary_row("Tags", if tags.is_empty() {
+                            "none".to_string()
+                        } else {
+                            tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" ")
+                        })}
+                        if plan.skipped > 0 {
+                            {summary_row("Skipped (archived or deleted)", plan.skipped.to_string())}
+                        }
+                        if plan.repeats_dropped > 0 {
+                            {summary_row("Repeating, imported once", plan.repeats_dropped.to_string())}
+                        }
+                    </dl>
+                    if props.offline {
+                        <p class="mb-4 text-sm text-yellow-300">{"Importing needs the backend, which is not reachable."}</p>
+                    }
+                    <div class="flex justify-end gap-2">
+                        <button
+                            onclick={on_close.reform(|_: MouseEvent| ())}
+                            class="px-4 py-2 text-sm text-gray-300 rounded hover:bg-gray-700"
+                        >
+                            {"Cancel"}
+                        </button>
+                        <button
+                            ref={start_ref}
+                            onclick={on_start}
+                            disabled={props.offline}
+                            class="px-4 py-2 text-sm font-semibold text-white bg-blue-600 rounded hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+                        >
+                            {format!("Import {}", plan.todo_count())}
+                        </button>
+                    </div>
+                </>
+            }
+        }
+        Some(Stage::Running { done, total, failures }) => {
+            let percent = if *total == 0 { 100 } else { done * 100 / total };
+            html! {
+                <>
+                    <h2 class="mb-4 text-lg font-semibold text-white">{"Importing…"}</h2>
+                    <div
+                        class="w-full h-2 mb-2 overflow-hidden bg-gray-700 rounded"
+                        role="progressbar"
+                        aria-valuemin="0"
+                        aria-valuemax={total.to_string()}
+                        aria-valuenow={done.to_string()}
+                    >
+                        <div class="h-full bg-blue-500" style={format!("width: {}%", percent)}></div>
+                    </div>
+                    <p class="mb-4 text-sm text-gray-400" aria-live="polite">
+                        {format!("Sent {} of {} todos", done, total)}
+                        if !failures.is_empty() {
+                            <span class="text-red-400">{format!(", {} failed", failures.len())}</span>
+                        }
+                    </p>
+                    {failure_list(failures)}
+                </>
+            }
+        }
+        Some(Stage::Finished { created, total, failures }) => html! {
+            <>
+                <h2 class="mb-1 text-lg font-semibold text-white">
+                    {format!("Created {} of {} todos", created, total)}
+                </h2>
+                <p class="mb-4 text-sm text-gray-400">
+                    if failures.is_empty() {
+                        {"Everything was imported."}
+                    } else {
+                        {format!("{} could not be created:", failures.len())}
+                    }
+                </p>
+                {failure_list(failures)}
+                <div class="flex justify-end">
+                    <button
+                        onclick={on_close.reform(|_: MouseEvent| ())}
+                        class="px-4 py-2 text-sm font-semibold text-white bg-blue-600 rounded hover:bg-blue-700"
+                    >
+                        {"Close"}
+                    </button>
+                </div>
+            </>
+        },
+    };
+
+    html! {
+        <>
+            <label
+                class={classes!(control_class, if props.offline { "opacity-50 cursor-not-allowed" } else { "cursor-pointer" })}
+                title="Todoist, Trello or Microsoft To Do JSON export"
+            >
+                {"Migrate…"}
+                <input type="file" accept=".json" disabled={props.offline} onchange={on_file} class="hidden" />
+            </label>
+
+            if stage.is_some() {
+                <div
+                    class="fixed inset-0 z-50 flex items-center justify-center bg-black/60"
+                    onclick={on_close.reform(|_: MouseEvent| ())}
+                    onkeydown={on_keydown}
+                >
+                    <div
+                        role="dialog"
+                        aria-modal="true"
+                        class="flex flex-col w-full max-w-xl max-h-[80vh] p-6 bg-gray-800 border border-gray-700 rounded-lg shadow-xl text-left"
+                        onclick={|e: MouseEvent| e.stop_propagation()}
+                    >
+                        {dialog}
+                    </div>
+                </div>
+            }
+        </>
+    }
+}
@@ -1,2 +1,17 @@
+pub mod bulk_bar;
 pub mod button;
-pub mod header;
\ No newline at end of file
+pub mod checklist;
+pub mod command_palette;
+pub mod confirm_dialog;
+pub mod header;
+pub mod list_sidebar;
+pub mod load_more;
+pub mod migration_import;
+pub mod protected_route;
+pub mod quick_add_preview;
+pub mod recurrence_fields;
+pub mod toast;
+pub mod todo_fields;
+pub mod todo_toolbar;
+pub mod transfer_menu;
+pub mod view_tabs;
@@ -0,0 +1,43 @@
+use crate::context::auth::use_auth;
+use crate::screens::login::LoginQuery;
+use yew::prelude::*;
+use yew_router::hooks::{use_location, use_navigator};
+
+#[derive(Properties, PartialEq)]
+pub struct ProtectedRouteProps {
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Renders its children only for authenticated users.
+///
+/// Anyone else is sent to `Route::Login` with the requested path (and query)
+/// in `?redirect=` so the login screen can bring them back afterwards. While
+/// an expired session is being refreshed nothing is rendered and nobody is
+/// redirected yet.
+#[function_component(ProtectedRoute)]
+pub fn protected_route(props: &ProtectedRouteProps) -> Html {
+    let auth = use_auth();
+    let navigator = use_navigator();
+    let location = use_location();
+    let allowed = auth.is_logged_in();
+    let refreshing = auth.is_refreshing();
+
+    use_effect_with((allowed, refreshing), move |(allowed, refreshing)| {
+        if !*allowed && !*refreshing {
+            if let (Some(navigator), Some(location)) = (navigator, location) {
+                let query = LoginQuery {
+                    redirect: Some(format!("{}{}", location.path(), location.query_str())),
+                };
+                let _ = navigator.replace_with_query(&crate::Route::Login, &query);
+            }
+        }
+        || ()
+    });
+
+    if allowed {
+        html! { { for props.children.iter() } }
+    } else {
+        html! {}
+    }
+}
@@ -0,0 +1,49 @@
+use yew::prelude::*;
+
+use crate::components::todo_fields::priority_badge_class;
+use crate::utils::quick_add::QuickAdd;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct QuickAddPreviewProps {
+    pub parsed: QuickAdd,
+    /// Name of the list `parsed.list` matched; `None` when no list has that name.
+    #[prop_or_default]
+    pub list_name: Option<AttrValue>,
+}
+
+/// Fields recognised in the quick-add text, shown while typing.
+#[function_component(QuickAddPreview)]
+pub fn quick_add_preview(props: &QuickAddPreviewProps) -> Html {
+    let parsed = &props.parsed;
+    let chip = "px-2 py-0.5 rounded text-xs";
+
+    html! {
+        <div class="flex flex-wrap items-center gap-2 text-sm">
+            <span class="text-gray-300 truncate">
+                if parsed.title.is_empty() {
+                    <span class="italic text-gray-500">{"No title"}</span>
+                } else {
+                    {&parsed.title}
+                }
+            </span>
+            if let Some(due) = parsed.describe_due() {
+                <span class={classes!(chip, "bg-gray-700", "text-gray-200")}>{format!("📅 {}", due)}</span>
+            }
+            if let Some(priority) = parsed.priority {
+                <span class={classes!(chip, priority_badge_class(priority))}>{priority.label()}</span>
+            }
+            { for parsed.tags.iter().map(|tag| html! {
+                <span class={classes!(chip, "bg-gray-700", "text-blue-300")}>{format!("#{}", tag)}</span>
+            }) }
+            if let Some(list) = &parsed.list {
+                if let Some(name) = &props.list_name {
+                    <span class={classes!(chip, "bg-gray-700", "text-gray-200")}>{format!("📁 {}", name)}</span>
+                } else {
+                    <span class={classes!(chip, "bg-yellow-900", "text-yellow-300")} title="Kept in the title">
+                        {format!("No list named \"{}\"", list)}
+                    </span>
+                }
+            }
+        </div>
+    }
+}
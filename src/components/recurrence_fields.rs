@@ -0,0 +1,166 @@
+use chrono::{Datelike, NaiveDate};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::api::models::parse_due_date;
+use crate::utils::dates::today;
+use crate::utils::recurrence::{Frequency, Recurrence, WEEKDAYS};
+
+/// Changes a rule using the value of the input that fired.
+type RuleEdit = Box<dyn Fn(&mut Recurrence, &str)>;
+
+const KINDS: [(&str, &str); 6] = [
+    ("none", "Does not repeat"),
+    ("daily", "Daily"),
+    ("weekdays", "Every weekday"),
+    ("weekly", "Weekly"),
+    ("monthly", "Monthly"),
+    ("after", "After completion"),
+];
+
+fn kind_of(recurrence: Option<&Recurrence>) -> &'static str {
+    match recurrence {
+        None => "none",
+        Some(r) if r.from_completion => "after",
+        Some(r) if *r == Recurrence::weekdays() => "weekdays",
+        Some(r) => match r.frequency {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+        },
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct RecurrenceFieldsProps {
+    /// Rule string, empty when the todo does not repeat.
+    pub value: String,
+    /// Due date of the draft, used to preset the weekday or day of month.
+    #[prop_or_default]
+    pub due_date: String,
+    pub onchange: Callback<String>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Repeat schedule picker producing a rule for [`Recurrence::parse`].
+#[function_component(RecurrenceFields)]
+pub fn recurrence_fields(props: &RecurrenceFieldsProps) -> Html {
+    let current = Recurrence::parse(&props.value);
+    let kind = kind_of(current.as_ref());
+    let start: NaiveDate = parse_due_date(&props.due_date).unwrap_or_else(today);
+
+    let on_kind = {
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let recurrence = match select.value().as_str() {
+                "daily" => Some(Recurrence::daily(1)),
+                "weekdays" => Some(Recurrence::weekdays()),
+                "weekly" => Some(Recurrence::weekly(1, vec![start.weekday()])),
+                "monthly" => Some(Recurrence::monthly(1, start.day())),
+                "after" => Some(Recurrence::after_completion(7)),
+                _ => None,
+            };
+            onchange.emit(recurrence.map(|r| r.to_rule()).unwrap_or_default());
+        })
+    };
+
+    // emits the current rule changed by `edit`
+    let update = |edit: RuleEdit| {
+        let current = current.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(mut recurrence) = current.clone() {
+                edit(&mut recurrence, &input.value());
+                onchange.emit(recurrence.to_rule());
+            }
+        })
+    };
+    let on_interval = update(Box::new(|r, value| r.interval = value.parse().unwrap_or(1).max(1)));
+    let on_month_day = update(Box::new(|r, value| {
+        r.month_day = Some(value.parse().unwrap_or(1u32).clamp(1, 31))
+    }));
+
+    let toggle_day = |day: chrono::Weekday| {
+        let current = current.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(mut recurrence) = current.clone() {
+                if recurrence.weekdays.contains(&day) {
+                    // a weekly rule keeps at least one day
+                    if recurrence.weekdays.len() > 1 {
+                        recurrence.weekdays.retain(|d| *d != day);
+                    }
+                } else {
+                    recurrence.weekdays.push(day);
+                    recurrence.weekdays.sort_by_key(|d| d.num_days_from_monday());
+                }
+                onchange.emit(recurrence.to_rule());
+            }
+        })
+    };
+
+    let field_class = "px-3 py-2 text-white bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500";
+    let unit = match kind {
+        "weekly" => "week(s)",
+        "monthly" => "month(s)",
+        _ => "day(s)",
+    };
+
+    html! {
+        <div class="flex flex-wrap items-center gap-2 text-sm text-gray-400">
+            {"Repeat"}
+            <select onchange={on_kind} disabled={props.disabled} class={field_class}>
+                { for KINDS.iter().map(|(value, label)| html! {
+                    <option value={*value} selected={*value == kind}>{*label}</option>
+                }) }
+            </select>
+            if let Some(recurrence) = current.as_ref().filter(|_| kind != "weekdays") {
+                {"every"}
+                <input
+                    type="number"
+                    min="1"
+                    value={recurrence.interval.to_string()}
+                    onchange={on_interval}
+                    disabled={props.disabled}
+                    class={classes!(field_class, "w-20")}
+                />
+                {unit}
+                if kind == "weekly" {
+                    <div class="flex gap-1">
+                        { for WEEKDAYS.iter().map(|day| {
+                            let selected = recurrence.weekdays.contains(day);
+                            html! {
+                                <button
+                                    type="button"
+                                    onclick={toggle_day(*day)}
+                                    disabled={props.disabled}
+                                    class={classes!(
+                                        "w-8", "py-1", "rounded",
+                                        if selected { "bg-blue-600 text-white" } else { "bg-gray-700 text-gray-400 hover:bg-gray-600" }
+                                    )}
+                                >
+                                    {day.to_string()[..2].to_string()}
+                                </button>
+                            }
+                        }) }
+                    </div>
+                }
+                if kind == "monthly" {
+                    {"on day"}
+                    <input
+                        type="number"
+                        min="1"
+                        max="31"
+                        value={recurrence.month_day.unwrap_or(start.day()).to_string()}
+                        onchange={on_month_day}
+                        disabled={props.disabled}
+                        class={classes!(field_class, "w-20")}
+                    />
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,82 @@
+use gloo_timers::callback::Timeout;
+use yew::prelude::*;
+
+/// How long a toast stays on screen before dismissing itself.
+const TOAST_MS: u32 = 5_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ToastKind {
+    #[default]
+    Error,
+    Info,
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ToastProps {
+    pub message: String,
+    pub ondismiss: Callback<()>,
+    #[prop_or_default]
+    pub kind: ToastKind,
+    /// Label of an optional action button, e.g. "Undo".
+    #[prop_or_default]
+    pub action: Option<AttrValue>,
+    #[prop_or_default]
+    pub onaction: Callback<()>,
+    /// Sits above the usual spot, for a second toast shown at the same time.
+    #[prop_or_default]
+    pub stacked: bool,
+}
+
+/// Toast pinned to the bottom-right corner; dismisses itself after a few seconds.
+#[function_component(Toast)]
+pub fn toast(props: &ToastProps) -> Html {
+    {
+        let ondismiss = props.ondismiss.clone();
+        // restart the timer whenever a new message replaces the current one
+        use_effect_with(props.message.clone(), move |_| {
+            let timeout = Timeout::new(TOAST_MS, move || ondismiss.emit(()));
+            move || drop(timeout)
+        });
+    }
+
+    let onclick = {
+        let ondismiss = props.ondismiss.clone();
+        Callback::from(move |_| ondismiss.emit(()))
+    };
+
+    let (tone, button_tone) = match props.kind {
+        ToastKind::Error => (
+            "text-red-200 border-red-500 bg-red-900/90",
+            "text-red-300 hover:text-red-100",
+        ),
+        ToastKind::Info => (
+            "text-gray-100 border-gray-600 bg-gray-800/95",
+            "text-gray-400 hover:text-white",
+        ),
+    };
+    let offset = if props.stacked { "bottom-24" } else { "bottom-6" };
+
+    html! {
+        <div
+            role={if props.kind == ToastKind::Error { "alert" } else { "status" }}
+            class={classes!("fixed", "z-50", "flex", "items-start", "gap-4", "px-4", "py-3", "text-sm", "border", "rounded-lg", "shadow-lg", offset, "right-6", "max-w-sm", tone)}
+        >
+            <span class="flex-1">{ &props.message }</span>
+            if let Some(label) = &props.action {
+                <button
+                    onclick={let onaction = props.onaction.clone(); move |_| onaction.emit(())}
+                    class="font-semibold text-blue-400 hover:text-blue-300"
+                >
+                    { label }
+                </button>
+            }
+            <button
+                {onclick}
+                class={button_tone}
+                title="Dismiss"
+            >
+                {"✕"}
+            </button>
+        </div>
+    }
+}
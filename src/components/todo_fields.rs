@@ -0,0 +1,266 @@
+use chrono::NaiveTime;
+
+use crate::api::models::{format_due, parse_due_date, Priority, Todo, TodoResponse, UpdateTodoType};
+use crate::components::recurrence_fields::RecurrenceFields;
+use crate::types::Status;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+/// Form state shared by the create and edit forms of the todo screen.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TodoDraft {
+    pub title: String,
+    pub description: String,
+    /// Value of the `<input type="date">`, empty when unset.
+    pub due_date: String,
+    /// Value of the `<input type="time">` (`HH:MM`), empty for a whole day.
+    pub due_time: String,
+    pub priority: Priority,
+    /// Comma separated, a leading `#` is optional.
+    pub tags: String,
+    /// Repeat rule, empty when the todo does not repeat.
+    pub recurrence: String,
+}
+
+impl TodoDraft {
+    pub fn from_todo(todo: &TodoResponse) -> Self {
+        Self {
+            title: todo.title.clone(),
+            description: todo.description.clone().unwrap_or_default(),
+            due_date: todo
+                .due()
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            due_time: todo
+                .due_time()
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_default(),
+            priority: todo.priority,
+            tags: todo.tags.join(", "),
+            recurrence: todo.recurrence.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Due date and time as stored on a todo, see [`format_due`].
+    pub fn due_value(&self) -> Option<String> {
+        let time = NaiveTime::parse_from_str(&self.due_time, "%H:%M").ok();
+        parse_due_date(&self.due_date).map(|date| format_due(date, time))
+    }
+
+    pub fn tag_list(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.tags.split(',') {
+            let tag = tag.trim().trim_start_matches('#').trim();
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    pub fn to_new_todo(&self) -> Todo {
+        let description = self.description.trim();
+        Todo {
+            title: self.title.trim().to_string(),
+            completed: false,
+            description: (!description.is_empty()).then(|| description.to_string()),
+            due_date: self.due_value(),
+            priority: self.priority,
+            tags: self.tag_list(),
+            checklist: Vec::new(),
+            list_id: None,
+            status: None,
+            recurrence: (!self.recurrence.is_empty()).then(|| self.recurrence.clone()),
+        }
+    }
+
+    /// Full update of every editable field; empty values clear the field.
+    pub fn to_update(&self, id: i64, completed: bool) -> UpdateTodoType {
+        UpdateTodoType {
+            id: Some(id),
+            title: Some(self.title.trim().to_string()),
+            description: Some(self.description.trim().to_string()),
+            completed: Some(completed),
+            status: None,
+            due_date: Some(self.due_value().unwrap_or_default()),
+            priority: Some(self.priority),
+            tags: Some(self.tag_list()),
+            checklist: None,
+            recurrence: Some(self.recurrence.clone()),
+            list_id: None,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct TodoFieldsProps {
+    pub draft: TodoDraft,
+    pub onchange: Callback<TodoDraft>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Description, due date and time, priority, tag and repeat inputs bound to a [`TodoDraft`].
+#[function_component(TodoFields)]
+pub fn todo_fields(props: &TodoFieldsProps) -> Html {
+    let on_description = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            onchange.emit(TodoDraft {
+                description: input.value(),
+                ..draft.clone()
+            });
+        })
+    };
+
+    let on_due_date = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            onchange.emit(TodoDraft {
+                due_date: input.value(),
+                ..draft.clone()
+            });
+        })
+    };
+
+    let on_due_time = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            onchange.emit(TodoDraft {
+                due_time: input.value(),
+                ..draft.clone()
+            });
+        })
+    };
+
+    let on_priority = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            onchange.emit(TodoDraft {
+                priority: Priority::from_str(&select.value()),
+                ..draft.clone()
+            });
+        })
+    };
+
+    let on_tags = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            onchange.emit(TodoDraft {
+                tags: input.value(),
+                ..draft.clone()
+            });
+        })
+    };
+
+    let on_recurrence = {
+        let draft = props.draft.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |recurrence: String| {
+            onchange.emit(TodoDraft {
+                recurrence,
+                ..draft.clone()
+            });
+        })
+    };
+
+    let field_class = "w-full px-3 py-2 text-white placeholder-gray-400 bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500";
+
+    html! {
+        <div class="grid grid-cols-1 gap-3 md:grid-cols-3">
+            <textarea
+                placeholder="Description"
+                rows="2"
+                value={props.draft.description.clone()}
+                oninput={on_description}
+                disabled={props.disabled}
+                class={classes!(field_class, "md:col-span-3")}
+            />
+            <label class="text-sm text-gray-400">
+                {"Due date"}
+                <div class="flex gap-2 mt-1">
+                    <input
+                        type="date"
+                        value={props.draft.due_date.clone()}
+                        onchange={on_due_date}
+                        disabled={props.disabled}
+                        class={field_class}
+                    />
+                    <input
+                        type="time"
+                        aria-label="Due time"
+                        value={props.draft.due_time.clone()}
+                        onchange={on_due_time}
+                        disabled={props.disabled || props.draft.due_date.is_empty()}
+                        class={classes!(field_class, "w-32")}
+                    />
+                </div>
+            </label>
+            <label class="text-sm text-gray-400">
+                {"Priority"}
+                <select
+                    onchange={on_priority}
+                    disabled={props.disabled}
+                    class={classes!(field_class, "mt-1")}
+                >
+                    { for Priority::ALL.iter().map(|priority| html! {
+                        <option
+                            value={priority.as_str()}
+                            selected={*priority == props.draft.priority}
+                        >
+                            {priority.label()}
+                        </option>
+                    }) }
+                </select>
+            </label>
+            <label class="text-sm text-gray-400">
+                {"Tags"}
+                <input
+                    type="text"
+                    placeholder="work, urgent"
+                    value={props.draft.tags.clone()}
+                    oninput={on_tags}
+                    disabled={props.disabled}
+                    class={classes!(field_class, "mt-1")}
+                />
+            </label>
+            <div class="md:col-span-3">
+                <RecurrenceFields
+                    value={props.draft.recurrence.clone()}
+                    due_date={props.draft.due_date.clone()}
+                    onchange={on_recurrence}
+                    disabled={props.disabled}
+                />
+            </div>
+        </div>
+    }
+}
+
+/// Tailwind classes of the badge shown for each priority.
+pub fn priority_badge_class(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "bg-gray-700 text-gray-300",
+        Priority::Medium => "bg-blue-900 text-blue-300",
+        Priority::High => "bg-orange-900 text-orange-300",
+        Priority::Urgent => "bg-red-900 text-red-300",
+    }
+}
+
+/// Tailwind classes of the badge shown for each status.
+pub fn status_badge_class(status: Status) -> &'static str {
+    match status {
+        Status::PENDING => "bg-yellow-900 text-yellow-300",
+        Status::IN_PROGRESS => "bg-blue-900 text-blue-300",
+        Status::DONE => "bg-green-900 text-green-300",
+    }
+}
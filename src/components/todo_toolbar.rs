@@ -0,0 +1,206 @@
+use crate::api::models::Priority;
+use crate::utils::todo_query::{SortDir, SortKey, StatusFilter, TodoQuery};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct TodoToolbarProps {
+    pub query: TodoQuery,
+    pub onchange: Callback<TodoQuery>,
+    /// Tags offered in the tag filter.
+    #[prop_or_default]
+    pub tags: Vec<String>,
+    pub visible: usize,
+    /// Todos loaded so far; filters and search only see these.
+    pub loaded: usize,
+    /// Todos on the server, loaded or not.
+    pub total: usize,
+    /// Loads the remaining pages; offered while some are missing.
+    #[prop_or_default]
+    pub onloadall: Callback<()>,
+    #[prop_or_default]
+    pub loading_all: bool,
+    /// Attached to the search field so a shortcut can focus it.
+    #[prop_or_default]
+    pub search_ref: NodeRef,
+}
+
+/// Status, search, tag, priority and sort controls for the todo list.
+#[function_component(TodoToolbar)]
+pub fn todo_toolbar(props: &TodoToolbarProps) -> Html {
+    let query = props.query.clone();
+
+    let on_search = {
+        let query = query.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            onchange.emit(TodoQuery {
+                q: input.value(),
+                ..query.clone()
+            });
+        })
+    };
+
+    let on_tag = {
+        let query = query.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            onchange.emit(TodoQuery {
+                tag: (!value.is_empty()).then_some(value),
+                ..query.clone()
+            });
+        })
+    };
+
+    let on_priority = {
+        let query = query.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            onchange.emit(TodoQuery {
+                priority: (!value.is_empty()).then(|| Priority::from_str(&value)),
+                ..query.clone()
+            });
+        })
+    };
+
+    let on_sort = {
+        let query = query.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            onchange.emit(TodoQuery {
+                sort: SortKey::from_str(&select.value()),
+                ..query.clone()
+            });
+        })
+    };
+
+    let on_toggle_dir = {
+        let query = query.clone();
+        let onchange = props.onchange.clone();
+        Callback::from(move |_| {
+            let dir = match query.dir {
+                SortDir::Asc => SortDir::Desc,
+                SortDir::Desc => SortDir::Asc,
+            };
+            onchange.emit(TodoQuery {
+                dir,
+                ..query.clone()
+            });
+        })
+    };
+
+    let on_clear = {
+        let onchange = props.onchange.clone();
+        Callback::from(move |_| onchange.emit(TodoQuery::default()))
+    };
+
+    let select_class = "px-3 py-2 text-sm text-white bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500";
+
+    html! {
+        <div class="p-4 mb-6 space-y-3 bg-gray-800 rounded-lg shadow-lg">
+            <div class="flex flex-wrap items-center gap-3">
+                <div class="flex overflow-hidden border border-gray-600 rounded">
+                    { for StatusFilter::ALL.iter().map(|status| {
+                        let onclick = {
+                            let query = query.clone();
+                            let onchange = props.onchange.clone();
+                            let status = *status;
+                            Callback::from(move |_| onchange.emit(TodoQuery { status, ..query.clone() }))
+                        };
+                        html! {
+                            <button
+                                {onclick}
+                                class={format!("px-3 py-2 text-sm {}",
+                                    if *status == query.status {
+                                        "bg-blue-600 text-white"
+                                    } else {
+                                        "bg-gray-700 text-gray-300 hover:bg-gray-600"
+                                    }
+                                )}
+                            >
+                                {status.label()}
+                            </button>
+                        }
+                    }) }
+                </div>
+                <input
+                    ref={props.search_ref.clone()}
+                    type="search"
+                    placeholder="Search title or description"
+                    value={query.q.clone()}
+                    oninput={on_search}
+                    class="flex-1 min-w-0 px-3 py-2 text-sm text-white placeholder-gray-400 bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500"
+                />
+            </div>
+            <div class="flex flex-wrap items-center gap-3">
+                <select onchange={on_tag} class={select_class}>
+                    <option value="" selected={query.tag.is_none()}>{"All tags"}</option>
+                    { for props.tags.iter().map(|tag| html! {
+                        <option
+                            value={tag.clone()}
+                            selected={query.tag.as_ref().is_some_and(|t| t.eq_ignore_ascii_case(tag))}
+                        >
+                            {format!("#{}", tag)}
+                        </option>
+                    }) }
+                </select>
+                <select onchange={on_priority} class={select_class}>
+                    <option value="" selected={query.priority.is_none()}>{"Any priority"}</option>
+                    { for Priority::ALL.iter().map(|priority| html! {
+                        <option
+                            value={priority.as_str()}
+                            selected={query.priority == Some(*priority)}
+                        >
+                            {priority.label()}
+                        </option>
+                    }) }
+                </select>
+                <select onchange={on_sort} class={select_class}>
+                    { for SortKey::ALL.iter().map(|key| html! {
+                        <option value={key.as_str()} selected={*key == query.sort}>
+                            {format!("Sort: {}", key.label())}
+                        </option>
+                    }) }
+                </select>
+                <button
+                    onclick={on_toggle_dir}
+                    class="px-3 py-2 text-sm text-gray-300 bg-gray-700 rounded hover:bg-gray-600"
+                    title="Toggle sort direction"
+                >
+                    {if query.dir == SortDir::Asc { "↑ Asc" } else { "↓ Desc" }}
+                </button>
+                <span class="ml-auto text-sm text-gray-400">
+                    if props.loaded < props.total {
+                        {format!("Showing {} of {} loaded ({} in all)", props.visible, props.loaded, props.total)}
+                    } else {
+                        {format!("Showing {} of {}", props.visible, props.total)}
+                    }
+                </span>
+                if props.loaded < props.total {
+                    <button
+                        onclick={props.onloadall.reform(|_| ())}
+                        disabled={props.loading_all}
+                        class="text-sm text-blue-400 hover:underline disabled:opacity-50"
+                        title="Filters and search only see loaded todos"
+                    >
+                        {if props.loading_all { "Loading…" } else { "Load all" }}
+                    </button>
+                }
+                if query != TodoQuery::default() {
+                    <button
+                        onclick={on_clear}
+                        class="text-sm text-blue-400 hover:underline"
+                    >
+                        {"Reset"}
+                    </button>
+                }
+            </div>
+        </div>
+    }
+}
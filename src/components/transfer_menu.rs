@@ -0,0 +1,269 @@
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+use crate::api::models::{Todo, TodoResponse};
+use crate::components::todo_fields::priority_badge_class;
+use crate::context::auth::use_auth;
+use crate::utils::dates::today;
+use crate::utils::todo_query::TodoQuery;
+use crate::utils::transfer::{export_todos, import_todos, TransferFormat};
+
+/// Most todos listed in the import preview; the rest are only counted.
+const PREVIEW_ROWS: usize = 100;
+
+/// Offers `text` as a file download named `name`.
+fn download(name: &str, mime_type: &str, text: &str) {
+    let parts = js_sys::Array::of1(&text.into());
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    let link = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("a").ok())
+        .and_then(|element| element.dyn_into::<web_sys::HtmlAnchorElement>().ok());
+    if let Some(link) = link {
+        link.set_href(&url);
+        link.set_download(name);
+        link.click();
+    }
+    // revoked later: some browsers start the download after `click` returns
+    Timeout::new(10_000, move || {
+        let _ = web_sys::Url::revoke_object_url(&url);
+    })
+    .forget();
+}
+
+#[derive(Clone, PartialEq)]
+struct ImportPreview {
+    file_name: String,
+    format: TransferFormat,
+    todos: Vec<Todo>,
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct TransferMenuProps {
+    /// Todos the export writes out, i.e. the ones currently shown.
+    pub todos: Vec<TodoResponse>,
+    /// Whether `todos` misses pages that are not loaded yet; the export then
+    /// reads every todo of `list_id` and filters them with `query` itself.
+    #[prop_or_default]
+    pub partial: bool,
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+    #[prop_or_default]
+    pub query: TodoQuery,
+    /// Receives the todos picked for import once the preview is confirmed.
+    pub onimport: Callback<Vec<Todo>>,
+    pub onerror: Callback<String>,
+}
+
+/// Export of the shown todos as JSON, CSV, Markdown or iCalendar, and import
+/// of such files after a preview.
+#[function_component(TransferMenu)]
+pub fn transfer_menu(props: &TransferMenuProps) -> Html {
+    let auth = use_auth();
+    let preview = use_state(|| Option::<ImportPreview>::None);
+    let exporting = use_state(|| false);
+    let confirm_ref = use_node_ref();
+
+    {
+        // focus the import button so Enter imports and Escape reaches the dialog
+        let confirm_ref = confirm_ref.clone();
+        use_effect_with(preview.is_some(), move |_| {
+            if let Some(button) = confirm_ref.cast::<web_sys::HtmlElement>() {
+                let _ = button.focus();
+            }
+            || ()
+        });
+    }
+
+    let on_export = {
+        let todos = props.todos.clone();
+        let partial = props.partial;
+        let list_id = props.list_id;
+        let query = props.query.clone();
+        let exporting = exporting.clone();
+        let client = auth.api_client();
+        let onerror = props.onerror.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            select.set_value("");
+            let Some(format) = TransferFormat::ALL
+                .into_iter()
+                .find(|format| format.extension() == value)
+            else {
+                return;
+            };
+            let name = format!("todos-{}.{}", today().format("%Y-%m-%d"), format.extension());
+            if !partial {
+                download(&name, format.mime_type(), &export_todos(&todos, format));
+                return;
+            }
+            let query = query.clone();
+            let exporting = exporting.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            exporting.set(true);
+            spawn_local(async move {
+                match client.list_all_todos(list_id).await {
+                    Ok(items) => download(&name, format.mime_type(), &export_todos(&query.apply(&items), format)),
+                    Err(err) => onerror.emit(format!("Could not export todos. {}", err)),
+                }
+                exporting.set(false);
+            });
+        })
+    };
+
+    let on_file = {
+        let preview = preview.clone();
+        let onerror = props.onerror.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // picking the same file again should fire another change
+            input.set_value("");
+            let preview = preview.clone();
+            let onerror = onerror.clone();
+            spawn_local(async move {
+                let Some(text) = JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string()) else {
+                    onerror.emit(format!("Could not read {}.", file.name()));
+                    return;
+                };
+                let format = TransferFormat::detect(&file.name(), &text);
+                match import_todos(&text, format) {
+                    Ok(todos) => preview.set(Some(ImportPreview {
+                        file_name: file.name(),
+                        format,
+                        todos,
+                    })),
+                    Err(err) => onerror.emit(format!("Could not import {}. {}", file.name(), err)),
+                }
+            });
+        })
+    };
+
+    let on_cancel = {
+        let preview = preview.clone();
+        Callback::from(move |_| preview.set(None))
+    };
+
+    let on_confirm = {
+        let preview = preview.clone();
+        let onimport = props.onimport.clone();
+        Callback::from(move |_| {
+            if let Some(import) = &*preview {
+                onimport.emit(import.todos.clone());
+            }
+            preview.set(None);
+        })
+    };
+
+    let on_keydown = {
+        let preview = preview.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                e.prevent_default();
+                preview.set(None);
+            }
+        })
+    };
+
+    let control_class = "px-2 py-1 text-xs text-gray-300 bg-gray-800 border border-gray-600 rounded hover:text-white cursor-pointer";
+
+    html! {
+        <>
+            <select
+                onchange={on_export}
+                disabled={*exporting}
+                class={control_class}
+                title="Download the todos shown, including pages not loaded yet"
+            >
+                <option value="" selected=true>{if *exporting { "Exporting…" } else { "Export…" }}</option>
+                { for TransferFormat::ALL.iter().map(|format| html! {
+                    <option value={format.extension()}>{format.label()}</option>
+                }) }
+            </select>
+            <label class={control_class} title="JSON, CSV, Markdown checklist or iCalendar">
+                {"Import…"}
+                <input type="file" accept=".json,.csv,.md,.markdown,.txt,.ics" onchange={on_file} class="hidden" />
+            </label>
+
+            if let Some(import) = &*preview {
+                <div
+                    class="fixed inset-0 z-50 flex items-center justify-center bg-black/60"
+                    onclick={on_cancel.clone()}
+                    onkeydown={on_keydown}
+                >
+                    <div
+                        role="dialog"
+                        aria-modal="true"
+                        class="flex flex-col w-full max-w-2xl max-h-[80vh] p-6 bg-gray-800 border border-gray-700 rounded-lg shadow-xl text-left"
+                        onclick={|e: MouseEvent| e.stop_propagation()}
+                    >
+                        <h2 class="mb-1 text-lg font-semibold text-white">
+                            {format!("Import {} todo(s)?", import.todos.len())}
+                        </h2>
+                        <p class="mb-4 text-sm text-gray-400">
+                            {format!("From {} ({})", import.file_name, import.format.label())}
+                        </p>
+                        <ul class="flex-1 mb-4 overflow-y-auto divide-y divide-gray-700">
+                            { for import.todos.iter().take(PREVIEW_ROWS).map(|todo| html! {
+                                <li class="flex flex-wrap items-center gap-2 py-2 text-sm">
+                                    <span class={if todo.completed { "text-green-400" } else { "text-gray-500" }}>
+                                        {if todo.completed { "☑" } else { "☐" }}
+                                    </span>
+                                    <span class={classes!("text-gray-200", todo.completed.then_some("line-through"))}>
+                                        {&todo.title}
+                                    </span>
+                                    <span class={classes!("px-2", "py-0.5", "rounded", "text-xs", priority_badge_class(todo.priority))}>
+                                        {todo.priority.label()}
+                                    </span>
+                                    if let Some(due) = &todo.due_date {
+                                        <span class="text-xs text-gray-400">{format!("📅 {}", due)}</span>
+                                    }
+                                    if !todo.checklist.is_empty() {
+                                        <span class="text-xs text-gray-400">{format!("☑ {}", todo.checklist.len())}</span>
+                                    }
+                                    { for todo.tags.iter().map(|tag| html! {
+                                        <span class="text-xs text-blue-300">{format!("#{}", tag)}</span>
+                                    }) }
+                                </li>
+                            }) }
+                        </ul>
+                        if import.todos.len() > PREVIEW_ROWS {
+                            <p class="mb-4 text-sm text-gray-400">
+                                {format!("…and {} more.", import.todos.len() - PREVIEW_ROWS)}
+                            </p>
+                        }
+                        <div class="flex justify-end gap-2">
+                            <button
+                                onclick={on_cancel}
+                                class="px-4 py-2 text-sm text-gray-300 rounded hover:bg-gray-700"
+                            >
+                                {"Cancel"}
+                            </button>
+                            <button
+                                ref={confirm_ref}
+                                onclick={on_confirm}
+                                class="px-4 py-2 text-sm font-semibold text-white bg-blue-600 rounded hover:bg-blue-700"
+                            >
+                                {format!("Import {}", import.todos.len())}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            }
+        </>
+    }
+}
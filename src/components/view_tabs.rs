@@ -0,0 +1,73 @@
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::Route;
+
+/// The ways a set of todos can be shown.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TodoView {
+    List,
+    Board,
+    Calendar,
+    Agenda,
+}
+
+impl TodoView {
+    pub const ALL: [TodoView; 4] = [
+        TodoView::List,
+        TodoView::Board,
+        TodoView::Calendar,
+        TodoView::Agenda,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TodoView::List => "List",
+            TodoView::Board => "Board",
+            TodoView::Calendar => "Calendar",
+            TodoView::Agenda => "Agenda",
+        }
+    }
+
+    /// Route of this view for `list_id`, or for all todos.
+    pub fn route(&self, list_id: Option<i64>) -> Route {
+        match (self, list_id) {
+            (TodoView::List, Some(id)) => Route::List { id },
+            (TodoView::List, None) => Route::Todo,
+            (TodoView::Board, Some(id)) => Route::ListBoard { id },
+            (TodoView::Board, None) => Route::Board,
+            (TodoView::Calendar, Some(id)) => Route::ListCalendar { id },
+            (TodoView::Calendar, None) => Route::Calendar,
+            (TodoView::Agenda, Some(id)) => Route::ListAgenda { id },
+            (TodoView::Agenda, None) => Route::Agenda,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ViewTabsProps {
+    pub current: TodoView,
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+}
+
+/// Switches between the list, board, calendar and agenda of the same todos.
+#[function_component(ViewTabs)]
+pub fn view_tabs(props: &ViewTabsProps) -> Html {
+    html! {
+        <nav class="inline-flex p-1 text-sm bg-gray-800 rounded-lg">
+            { for TodoView::ALL.iter().map(|view| {
+                let class = if *view == props.current {
+                    "px-3 py-1 text-white bg-gray-700 rounded"
+                } else {
+                    "px-3 py-1 text-gray-400 rounded hover:text-white"
+                };
+                html! {
+                    <Link<Route> to={view.route(props.list_id)} classes={class}>
+                        {view.label()}
+                    </Link<Route>>
+                }
+            }) }
+        </nav>
+    }
+}
@@ -0,0 +1,220 @@
+use std::rc::Rc;
+
+use crate::api::client::PAGE_SIZE;
+use crate::api::models::{TodoPage, TodoPosition, TodoResponse};
+use yew::Reducible;
+
+/// Todos loaded so far plus the paging cursor for the next request.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TodoList {
+    pub items: Vec<TodoResponse>,
+    /// Total reported by the backend, if any.
+    pub total: Option<usize>,
+    pub next_offset: usize,
+    pub has_more: bool,
+}
+
+pub enum TodoListAction {
+    /// Replaces everything with the first page.
+    Reset(TodoPage),
+    /// Appends the following page, skipping todos that are already loaded.
+    Append(TodoPage),
+    /// Replaces the todo with the same id, or adds it when it is new.
+    Upsert(TodoResponse),
+    /// Replaces the todo with the same id only if it is already loaded.
+    Refresh(TodoResponse),
+    /// Swaps the todo with the given id for another one, keeping its position.
+    Replace(i64, TodoResponse),
+    Remove(i64),
+    /// Puts a removed todo back at its previous index.
+    Restore(usize, TodoResponse),
+    /// Sets the manual-order position of the given todos.
+    Reposition(Vec<(i64, Option<f64>)>),
+}
+
+/// Smallest gap between neighbours before the whole list gets renumbered.
+const MIN_GAP: f64 = 1e-6;
+
+/// Positions to save when moving `ordered[from]` to index `to`.
+///
+/// Normally only the moved todo changes, taking the midpoint between its new
+/// neighbours. When positions are missing or too close together every saved
+/// todo is renumbered instead.
+pub fn plan_move(ordered: &[TodoResponse], from: usize, to: usize) -> Vec<TodoPosition> {
+    if from == to || from >= ordered.len() || to >= ordered.len() {
+        return Vec::new();
+    }
+    let mut order: Vec<&TodoResponse> = ordered.iter().collect();
+    let moved = order.remove(from);
+    order.insert(to, moved);
+
+    let previous = to.checked_sub(1).map(|index| order[index].position);
+    let next = order.get(to + 1).map(|todo| todo.position);
+    let position = match (previous, next) {
+        (Some(Some(before)), Some(Some(after))) if after - before > MIN_GAP => {
+            Some((before + after) / 2.0)
+        }
+        (None, Some(Some(after))) => Some(after - 1.0),
+        (Some(Some(before)), None) => Some(before + 1.0),
+        _ => None,
+    };
+
+    match position {
+        Some(position) => vec![TodoPosition {
+            id: moved.id,
+            position,
+        }],
+        None => order
+            .iter()
+            .enumerate()
+            .filter(|(_, todo)| todo.id > 0)
+            .map(|(index, todo)| TodoPosition {
+                id: todo.id,
+                position: index as f64,
+            })
+            .collect(),
+    }
+}
+
+impl TodoList {
+    /// List restored from the offline cache; paging restarts once the backend answers.
+    pub fn cached(items: Vec<TodoResponse>) -> Self {
+        TodoList {
+            next_offset: items.iter().filter(|todo| todo.id > 0).count(),
+            items,
+            total: None,
+            has_more: false,
+        }
+    }
+
+    fn with_page(mut self, page: TodoPage) -> Self {
+        let received = page.items.len();
+        for item in page.items {
+            if !self.items.iter().any(|todo| todo.id == item.id) {
+                self.items.push(item);
+            }
+        }
+        self.next_offset += received;
+        self.total = page.total;
+        self.has_more = match page.total {
+            Some(total) => self.next_offset < total,
+            None => received == PAGE_SIZE,
+        };
+        self
+    }
+}
+
+impl Reducible for TodoList {
+    type Action = TodoListAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let list = (*self).clone();
+        let list = match action {
+            TodoListAction::Reset(page) => TodoList::default().with_page(page),
+            TodoListAction::Append(page) => list.with_page(page),
+            TodoListAction::Upsert(todo) => {
+                let mut list = list;
+                match list.items.iter_mut().find(|item| item.id == todo.id) {
+                    Some(item) => *item = todo,
+                    None => {
+                        list.items.push(todo);
+                        list.total = list.total.map(|total| total + 1);
+                    }
+                }
+                list
+            }
+            TodoListAction::Refresh(todo) => {
+                let mut list = list;
+                if let Some(item) = list.items.iter_mut().find(|item| item.id == todo.id) {
+                    *item = todo;
+                }
+                list
+            }
+            TodoListAction::Reposition(positions) => {
+                let mut list = list;
+                for (id, position) in positions {
+                    if let Some(item) = list.items.iter_mut().find(|item| item.id == id) {
+                        item.position = position;
+                    }
+                }
+                list
+            }
+            TodoListAction::Remove(id) => {
+                let mut list = list;
+                let before = list.items.len();
+                list.items.retain(|item| item.id != id);
+                if list.items.len() < before {
+                    // unsaved todos (negative ids) never counted towards the offset
+                    if id > 0 {
+                        // the server-side offset of every later todo moves up by one
+                        list.next_offset = list.next_offset.saturating_sub(1);
+                    }
+                    list.total = list.total.map(|total| total.saturating_sub(1));
+                }
+                list
+            }
+            TodoListAction::Replace(id, todo) => {
+                let mut list = list;
+                list.items.retain(|item| item.id != todo.id || item.id == id);
+                match list.items.iter_mut().find(|item| item.id == id) {
+                    Some(item) => *item = todo,
+                    None => list.items.push(todo),
+                }
+                list
+            }
+            TodoListAction::Restore(index, todo) => {
+                let mut list = list;
+                if !list.items.iter().any(|item| item.id == todo.id) {
+                    if todo.id > 0 {
+                        list.next_offset += 1;
+                    }
+                    list.total = list.total.map(|total| total + 1);
+                    let index = index.min(list.items.len());
+                    list.items.insert(index, todo);
+                }
+                list
+            }
+        };
+        Rc::new(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::fixtures::todo;
+
+    fn placed(positions: &[(i64, Option<f64>)]) -> Vec<TodoResponse> {
+        positions
+            .iter()
+            .map(|&(id, position)| TodoResponse {
+                position,
+                ..todo(id, "Todo")
+            })
+            .collect()
+    }
+
+    fn at(id: i64, position: f64) -> TodoPosition {
+        TodoPosition { id, position }
+    }
+
+    #[test]
+    fn moves_only_the_dragged_todo() {
+        let todos = placed(&[(1, Some(1.0)), (2, Some(2.0)), (3, Some(3.0))]);
+        assert_eq!(plan_move(&todos, 2, 1), vec![at(3, 1.5)]);
+        assert_eq!(plan_move(&todos, 2, 0), vec![at(3, 0.0)]);
+        assert_eq!(plan_move(&todos, 0, 2), vec![at(1, 4.0)]);
+        assert!(plan_move(&todos, 1, 1).is_empty());
+        assert!(plan_move(&todos, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn renumbers_when_there_is_no_room() {
+        let crowded = placed(&[(1, Some(1.0)), (2, Some(1.0 + MIN_GAP / 2.0)), (3, Some(3.0))]);
+        assert_eq!(plan_move(&crowded, 2, 1), vec![at(1, 0.0), at(3, 1.0), at(2, 2.0)]);
+
+        // unsaved todos (negative ids) keep their slot but get no position
+        let unplaced = placed(&[(1, None), (-1, None), (2, Some(5.0))]);
+        assert_eq!(plan_move(&unplaced, 2, 0), vec![at(2, 0.0), at(1, 1.0)]);
+    }
+}
@@ -1,7 +1,85 @@
 // auth.rs
+use gloo_timers::callback::Timeout;
+use jsonwebtoken::dangerous::insecure_decode;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-pub type AuthCtx = UseStateHandle<Option<String>>; // lưu token (hoặc sub)
+use crate::api::client::{SharedSession, TodoApiClient};
+use crate::api::models::LoginResponse;
+use crate::utils::storage::{load_item, store_item};
+
+const TOKEN_KEY: &str = "auth_token";
+const REFRESH_TOKEN_KEY: &str = "refresh_token";
+/// Longest delay `setTimeout` accepts (about 24.8 days); later expiries are re-checked after it.
+const MAX_TIMEOUT_MS: i64 = i32::MAX as i64;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Claims {
+    pub sub: Option<String>,
+    /// Expiry as seconds since the Unix epoch.
+    #[serde(default)]
+    pub exp: Option<i64>,
+    /// Issued-at as seconds since the Unix epoch.
+    #[serde(default)]
+    pub iat: Option<i64>,
+}
+
+impl Claims {
+    /// Milliseconds left before `exp`, or `None` when the token never expires.
+    pub fn remaining_ms(&self) -> Option<i64> {
+        self.exp
+            .map(|exp| exp * 1000 - chrono::Utc::now().timestamp_millis())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining_ms().is_some_and(|ms| ms <= 0)
+    }
+}
+
+/// Authentication state shared through [`AuthProvider`]; read it with [`use_auth`].
+#[derive(Clone, PartialEq)]
+pub struct AuthCtx {
+    pub token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub claims: Option<Claims>,
+    /// Set when the session ended because the token expired or was rejected.
+    pub session_expired: bool,
+    /// Stores the given tokens and marks the user as logged in.
+    pub login: Callback<LoginResponse>,
+    /// Clears the stored token.
+    pub logout: Callback<()>,
+    /// Clears the stored token and flags the session as expired.
+    pub expire: Callback<()>,
+    /// Tokens and refresh in flight, shared by every [`AuthCtx::api_client`].
+    session: SharedSession,
+}
+
+impl AuthCtx {
+    /// True when a token is stored, decodes as a JWT and has not expired.
+    pub fn is_logged_in(&self) -> bool {
+        self.token.is_some() && self.claims.as_ref().is_some_and(|c| !c.is_expired())
+    }
+
+    /// True while an expired token is being renewed with the stored refresh
+    /// token; the session either comes back or ends with [`AuthCtx::expire`].
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_token.is_some() && self.claims.as_ref().is_some_and(Claims::is_expired)
+    }
+
+    /// Subject of the token, keying per-user storage; empty when logged out.
+    pub fn user(&self) -> String {
+        self.claims
+            .as_ref()
+            .and_then(|claims| claims.sub.clone())
+            .unwrap_or_default()
+    }
+
+    /// API client authorized with the current token that refreshes it on 401.
+    pub fn api_client(&self) -> TodoApiClient {
+        TodoApiClient::for_session(self.session.clone(), self.login.clone())
+    }
+}
 
 #[derive(Properties, PartialEq)]
 pub struct AuthProviderProps {
@@ -9,26 +87,100 @@ pub struct AuthProviderProps {
     pub children: Children,
 }
 
+fn decode_claims(token: &str) -> Option<Claims> {
+    insecure_decode::<Claims>(token).ok().map(|data| data.claims)
+}
+
 #[function_component(AuthProvider)]
 pub fn auth_provider(props: &AuthProviderProps) -> Html {
-    let token = use_state(|| None::<String>);
+    // đọc token từ localStorage ngay khi khởi tạo để tránh nháy UI
+    let token = use_state(|| load_item(TOKEN_KEY));
+    let refresh_token = use_state(|| load_item(REFRESH_TOKEN_KEY));
+    let session_expired = use_state(|| false);
+    // bumped to re-arm the expiry timer when the expiry is too far off for one timeout
+    let expiry_check = use_state(|| 0u32);
 
-    // lúc app khởi động: load từ localStorage vào context
-    {
+    let login = {
         let token = token.clone();
-        use_effect(move || {
-            if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                if let Ok(Some(t)) = storage.get_item("auth_token") {
-                    token.set(Some(t));
+        let refresh_token = refresh_token.clone();
+        let session_expired = session_expired.clone();
+        Callback::from(move |tokens: LoginResponse| {
+            store_item(TOKEN_KEY, Some(&tokens.token));
+            store_item(REFRESH_TOKEN_KEY, tokens.refresh_token.as_deref());
+            session_expired.set(false);
+            token.set(Some(tokens.token));
+            refresh_token.set(tokens.refresh_token);
+        })
+    };
+
+    let end_session = {
+        let token = token.clone();
+        let refresh_token = refresh_token.clone();
+        let session_expired = session_expired.clone();
+        Callback::from(move |expired: bool| {
+            store_item(TOKEN_KEY, None);
+            store_item(REFRESH_TOKEN_KEY, None);
+            session_expired.set(expired);
+            token.set(None);
+            refresh_token.set(None);
+        })
+    };
+    let logout = end_session.reform(|_| false);
+    let expire = end_session.reform(|_| true);
+
+    let claims = token.as_deref().and_then(decode_claims);
+    let session = (*use_memo((), |_| SharedSession::default())).clone();
+    session.set_tokens((*token).clone(), (*refresh_token).clone());
+
+    let context = AuthCtx {
+        token: (*token).clone(),
+        refresh_token: (*refresh_token).clone(),
+        claims,
+        session_expired: *session_expired,
+        login,
+        logout,
+        expire,
+        session,
+    };
+
+    // hết hạn thì refresh, không được thì tự động logout
+    {
+        let context = context.clone();
+        let expiry_check = expiry_check.clone();
+        use_effect_with((context.claims.clone(), *expiry_check), move |(claims, check)| {
+            let remaining = claims.as_ref().and_then(Claims::remaining_ms);
+            let check = *check;
+            let timeout = remaining.map(|ms| {
+                if ms > MAX_TIMEOUT_MS {
+                    return Timeout::new(MAX_TIMEOUT_MS as u32, move || {
+                        expiry_check.set(check.wrapping_add(1))
+                    });
                 }
-            }
-            || ()
+                Timeout::new(ms.max(0) as u32, move || {
+                    if context.refresh_token.is_none() {
+                        context.expire.emit(());
+                        return;
+                    }
+                    spawn_local(async move {
+                        if context.api_client().refresh_session().await.is_err() {
+                            context.expire.emit(());
+                        }
+                    });
+                })
+            });
+            move || drop(timeout)
         });
     }
 
     html! {
-        <ContextProvider<AuthCtx> context={token}>
+        <ContextProvider<AuthCtx> {context}>
             { for props.children.iter() }
         </ContextProvider<AuthCtx>>
     }
 }
+
+/// Returns the current [`AuthCtx`]. Must be called below an [`AuthProvider`].
+#[hook]
+pub fn use_auth() -> AuthCtx {
+    use_context::<AuthCtx>().expect("AuthCtx missing, wrap the app in <AuthProvider>")
+}
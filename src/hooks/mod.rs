@@ -0,0 +1,6 @@
+pub mod use_all_todos;
+pub mod use_online;
+pub mod use_paged_todos;
+pub mod use_report_error;
+pub mod use_shortcuts;
+pub mod use_todo_events;
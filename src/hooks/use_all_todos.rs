@@ -0,0 +1,75 @@
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api::error::ApiError;
+use crate::api::models::TodoResponse;
+use crate::api::sync::{cache_key, load_cache};
+use crate::context::auth::use_auth;
+use crate::hooks::use_online::use_online;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AllTodos {
+    pub items: Vec<TodoResponse>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// Every todo of `list_id` (or of all lists), read page by page.
+///
+/// Date views need the whole set rather than the first page; until it
+/// arrives, or while offline, the list view's cache is shown.
+#[hook]
+pub fn use_all_todos(list_id: Option<i64>) -> UseStateHandle<AllTodos> {
+    let auth = use_auth();
+    let online = use_online();
+    let state = {
+        let cache_key = cache_key(&auth.user(), list_id);
+        use_state(move || AllTodos {
+            items: load_cache(&cache_key),
+            ..AllTodos::default()
+        })
+    };
+
+    {
+        let state = state.clone();
+        let client = auth.api_client();
+        let expire = auth.expire.clone();
+        use_effect_with((auth.is_logged_in(), online), move |(_, online)| {
+            if *online {
+                state.set(AllTodos {
+                    loading: true,
+                    error: None,
+                    ..(*state).clone()
+                });
+                spawn_local(async move {
+                    let result = client.list_all_todos(list_id).await;
+                    match result {
+                        Ok(items) => state.set(AllTodos {
+                            items,
+                            loading: false,
+                            error: None,
+                        }),
+                        // keep showing the cached todos
+                        Err(ApiError::Network(_)) => state.set(AllTodos {
+                            loading: false,
+                            ..(*state).clone()
+                        }),
+                        Err(err) => {
+                            if matches!(err, ApiError::Unauthorized(_)) {
+                                expire.emit(());
+                            }
+                            state.set(AllTodos {
+                                loading: false,
+                                error: Some(format!("Could not load todos. {}", err)),
+                                ..(*state).clone()
+                            });
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    state
+}
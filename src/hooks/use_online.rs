@@ -0,0 +1,43 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+fn navigator_online() -> bool {
+    web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true)
+}
+
+/// Tracks `navigator.onLine`, re-rendering on the window's `online`/`offline` events.
+///
+/// `true` only means a network is available; the backend may still be unreachable.
+#[hook]
+pub fn use_online() -> bool {
+    let online = use_state(navigator_online);
+
+    {
+        let online = online.clone();
+        use_effect_with((), move |_| {
+            let listeners = web_sys::window().map(|window| {
+                let on_change = Closure::<dyn Fn()>::new(move || online.set(navigator_online()));
+                for event in ["online", "offline"] {
+                    let _ = window
+                        .add_event_listener_with_callback(event, on_change.as_ref().unchecked_ref());
+                }
+                (window, on_change)
+            });
+            move || {
+                if let Some((window, on_change)) = listeners {
+                    for event in ["online", "offline"] {
+                        let _ = window.remove_event_listener_with_callback(
+                            event,
+                            on_change.as_ref().unchecked_ref(),
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    *online
+}
@@ -0,0 +1,134 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::console;
+use yew::prelude::*;
+
+use crate::api::client::PAGE_SIZE;
+use crate::api::error::ApiError;
+use crate::api::models::TodoPage;
+use crate::api::sync::{cache_key, load_cache};
+use crate::containers::todo_list::{TodoList, TodoListAction};
+use crate::context::auth::use_auth;
+
+/// Todos of one list (or of all lists) as the list and board views page through them.
+pub struct PagedTodos {
+    pub todos: UseReducerHandle<TodoList>,
+    /// Where the loaded todos are cached for offline use.
+    pub cache_key: String,
+    pub loading: UseStateHandle<bool>,
+    pub loading_more: UseStateHandle<bool>,
+    /// The network is up but the backend did not answer.
+    pub unreachable: UseStateHandle<bool>,
+    /// Replaces the loaded todos with the first page.
+    pub reload: Callback<()>,
+    pub load_more: Callback<()>,
+    /// Loads every remaining page, so filters and counts see all todos.
+    pub load_all: Callback<()>,
+}
+
+/// Pages through the todos of `list_id`, starting from the todos cached for
+/// the same user and list. Nothing is fetched until `reload` is called.
+#[hook]
+pub fn use_paged_todos(list_id: Option<i64>, onerror: Callback<(&'static str, ApiError)>) -> PagedTodos {
+    let auth = use_auth();
+    let client = auth.api_client();
+    let cache_key = cache_key(&auth.user(), list_id);
+    let todos = {
+        let cache_key = cache_key.clone();
+        use_reducer(move || TodoList::cached(load_cache(&cache_key)))
+    };
+    let loading = use_state(|| false);
+    let loading_more = use_state(|| false);
+    let unreachable = use_state(|| false);
+
+    let reload = {
+        let todos = todos.clone();
+        let loading = loading.clone();
+        let unreachable = unreachable.clone();
+        let client = client.clone();
+        let onerror = onerror.clone();
+        Callback::from(move |_| {
+            let todos = todos.clone();
+            let loading = loading.clone();
+            let unreachable = unreachable.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            loading.set(true);
+            spawn_local(async move {
+                match client.list_todos_page(0, PAGE_SIZE, list_id).await {
+                    Ok(page) => {
+                        unreachable.set(false);
+                        todos.dispatch(TodoListAction::Reset(page));
+                    }
+                    Err(ApiError::Network(err)) => {
+                        // keep showing the cached todos
+                        console::log_1(&format!("Backend unreachable: {}", err).into());
+                        unreachable.set(true);
+                    }
+                    Err(err) => onerror.emit(("Could not load todos.", err)),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let load_all = {
+        let todos = todos.clone();
+        let loading_more = loading_more.clone();
+        let client = client.clone();
+        let onerror = onerror.clone();
+        Callback::from(move |_| {
+            if *loading_more {
+                return;
+            }
+            let todos = todos.clone();
+            let loading_more = loading_more.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            loading_more.set(true);
+            spawn_local(async move {
+                match client.list_all_todos(list_id).await {
+                    Ok(items) => todos.dispatch(TodoListAction::Reset(TodoPage {
+                        total: Some(items.len()),
+                        items,
+                    })),
+                    Err(err) => onerror.emit(("Could not load all todos.", err)),
+                }
+                loading_more.set(false);
+            });
+        })
+    };
+
+    let load_more = {
+        let todos = todos.clone();
+        let loading_more = loading_more.clone();
+        let offset = todos.next_offset;
+        Callback::from(move |_| {
+            if *loading_more {
+                return;
+            }
+            let todos = todos.clone();
+            let loading_more = loading_more.clone();
+            let client = client.clone();
+            let onerror = onerror.clone();
+            loading_more.set(true);
+            spawn_local(async move {
+                match client.list_todos_page(offset, PAGE_SIZE, list_id).await {
+                    Ok(page) => todos.dispatch(TodoListAction::Append(page)),
+                    Err(err) => onerror.emit(("Could not load more todos.", err)),
+                }
+                loading_more.set(false);
+            });
+        })
+    };
+
+    PagedTodos {
+        todos,
+        cache_key,
+        loading,
+        loading_more,
+        unreachable,
+        reload,
+        load_more,
+        load_all,
+    }
+}
@@ -0,0 +1,20 @@
+use yew::prelude::*;
+
+use crate::api::error::ApiError;
+use crate::context::auth::use_auth;
+
+/// Callback reporting a failed request as "`action` `error`" in `error`.
+///
+/// A rejected token ends the session instead, and `ProtectedRoute` sends the
+/// user back to login.
+#[hook]
+pub fn use_report_error(error: UseStateHandle<Option<String>>) -> Callback<(&'static str, ApiError)> {
+    let expire = use_auth().expire;
+    Callback::from(move |(action, err): (&'static str, ApiError)| {
+        if matches!(err, ApiError::Unauthorized(_)) {
+            expire.emit(());
+        } else {
+            error.set(Some(format!("{} {}", action, err)));
+        }
+    })
+}
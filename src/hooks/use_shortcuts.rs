@@ -0,0 +1,109 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::KeyboardEvent;
+use yew::prelude::*;
+
+/// A key bound to an action while the screen using it is shown.
+#[derive(Clone, PartialEq)]
+pub struct Shortcut {
+    /// `KeyboardEvent.key` value, e.g. `"n"`, `"/"`, `"Delete"` or `"Escape"`.
+    pub key: &'static str,
+    /// Needs Ctrl (or ⌘ on macOS).
+    pub ctrl: bool,
+    /// What the action does, also shown in the command palette.
+    pub label: &'static str,
+    pub run: Callback<()>,
+}
+
+impl Shortcut {
+    pub fn new(key: &'static str, label: &'static str, run: Callback<()>) -> Self {
+        Shortcut {
+            key,
+            ctrl: false,
+            label,
+            run,
+        }
+    }
+
+    pub fn with_ctrl(self) -> Self {
+        Shortcut { ctrl: true, ..self }
+    }
+
+    /// Key as shown to the user, e.g. "Ctrl+K".
+    pub fn hint(&self) -> String {
+        let key = match self.key {
+            "Escape" => "Esc".to_string(),
+            "Delete" => "Del".to_string(),
+            key => key.to_uppercase(),
+        };
+        if self.ctrl {
+            format!("Ctrl+{}", key)
+        } else {
+            key
+        }
+    }
+
+    fn matches(&self, e: &KeyboardEvent) -> bool {
+        let ctrl = e.ctrl_key() || e.meta_key();
+        ctrl == self.ctrl && !e.alt_key() && e.key().eq_ignore_ascii_case(self.key)
+    }
+}
+
+/// Whether the key goes to a text field rather than to a shortcut.
+fn typing_into(e: &KeyboardEvent) -> bool {
+    let Some(target) = e.target().and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok()) else {
+        return false;
+    };
+    matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") || target.is_content_editable()
+}
+
+/// Whether a modal dialog is open; it handles its own keys.
+fn modal_open() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector("[aria-modal=\"true\"]").ok().flatten())
+        .is_some()
+}
+
+/// Runs the matching shortcut on every `keydown` of the window.
+///
+/// Plain keys are ignored while typing into a field, where Escape and Ctrl
+/// combinations still work, and while a modal dialog is open. Pass an empty
+/// list to pause the shortcuts altogether.
+#[hook]
+pub fn use_shortcuts(shortcuts: Vec<Shortcut>) {
+    // the listener stays registered; the callbacks change every render
+    let current = use_mut_ref(Vec::<Shortcut>::new);
+    *current.borrow_mut() = shortcuts;
+
+    use_effect_with((), move |_| {
+        let current = Rc::clone(&current);
+        let listener = web_sys::window().map(|window| {
+            let on_keydown = Closure::<dyn Fn(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+                // already handled, e.g. by the Escape of a dialog
+                if e.default_prevented() {
+                    return;
+                }
+                let ctrl = e.ctrl_key() || e.meta_key();
+                if !ctrl && (modal_open() || e.key() != "Escape" && typing_into(&e)) {
+                    return;
+                }
+                let matched = current.borrow().iter().find(|s| s.matches(&e)).cloned();
+                if let Some(shortcut) = matched {
+                    e.prevent_default();
+                    shortcut.run.emit(());
+                }
+            });
+            let _ = window.add_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+            (window, on_keydown)
+        });
+        move || {
+            if let Some((window, on_keydown)) = listener {
+                let _ = window
+                    .remove_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref());
+            }
+        }
+    });
+}
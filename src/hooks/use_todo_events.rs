@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{console, CloseEvent, Event, MessageEvent, WebSocket};
+use yew::prelude::*;
+
+use crate::api::models::TodoEvent;
+use crate::utils::ws_url;
+
+const LIVE_PATH: &str = "api/v1/todo/live";
+/// First reconnect delay; doubled after every failed attempt up to `MAX_BACKOFF_MS`.
+const BASE_BACKOFF_MS: u32 = 1_000;
+const MAX_BACKOFF_MS: u32 = 30_000;
+
+fn backoff_ms(attempt: u32) -> u32 {
+    BASE_BACKOFF_MS
+        .saturating_mul(1 << attempt.min(5))
+        .min(MAX_BACKOFF_MS)
+}
+
+/// An open socket and the JS callbacks that must live as long as it does.
+struct Connection {
+    socket: WebSocket,
+    _onopen: Closure<dyn FnMut(Event)>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onclose: Closure<dyn FnMut(CloseEvent)>,
+}
+
+impl Drop for Connection {
+    /// Detaches every handler before its closure is freed, so events still
+    /// arriving on the closing socket can't call into a dropped `Closure`.
+    fn drop(&mut self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+struct LiveChannel {
+    url: String,
+    on_event: Callback<TodoEvent>,
+    on_status: Callback<bool>,
+    connection: Option<Connection>,
+    retry: Option<Timeout>,
+    attempt: u32,
+    stopped: bool,
+}
+
+fn connect(channel: &Rc<RefCell<LiveChannel>>) {
+    let mut live = channel.borrow_mut();
+    if live.stopped {
+        return;
+    }
+    let socket = match WebSocket::new(&live.url) {
+        Ok(socket) => socket,
+        Err(_) => {
+            drop(live);
+            schedule_reconnect(channel);
+            return;
+        }
+    };
+
+    let weak = Rc::downgrade(channel);
+    let onopen = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+        if let Some(channel) = weak.upgrade() {
+            let mut live = channel.borrow_mut();
+            live.attempt = 0;
+            live.on_status.emit(true);
+        }
+    });
+    let on_event = live.on_event.clone();
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        let Some(text) = event.data().as_string() else {
+            return;
+        };
+        match serde_json::from_str::<TodoEvent>(&text) {
+            Ok(todo_event) => on_event.emit(todo_event),
+            Err(err) => console::log_1(&format!("Ignoring live message: {}", err).into()),
+        }
+    });
+    let weak: Weak<RefCell<LiveChannel>> = Rc::downgrade(channel);
+    let onclose = Closure::<dyn FnMut(CloseEvent)>::new(move |_: CloseEvent| {
+        if let Some(channel) = weak.upgrade() {
+            // the dead connection is dropped by the next `connect`, not from inside its own handler,
+            // which would free the closure that is running
+            channel.borrow().on_status.emit(false);
+            schedule_reconnect(&channel);
+        }
+    });
+    // errors are always followed by `close`, which handles the reconnect
+    socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+    socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+
+    live.connection = Some(Connection {
+        socket,
+        _onopen: onopen,
+        _onmessage: onmessage,
+        _onclose: onclose,
+    });
+}
+
+fn schedule_reconnect(channel: &Rc<RefCell<LiveChannel>>) {
+    let mut live = channel.borrow_mut();
+    if live.stopped {
+        return;
+    }
+    let delay = backoff_ms(live.attempt);
+    live.attempt += 1;
+    let weak = Rc::downgrade(channel);
+    live.retry = Some(Timeout::new(delay, move || {
+        if let Some(channel) = weak.upgrade() {
+            connect(&channel);
+        }
+    }));
+}
+
+/// Subscribes to the backend's live todo channel while `token` is set.
+///
+/// The socket reconnects with exponential backoff (1s up to 30s) whenever it
+/// drops. Browsers can't send headers on a WebSocket handshake, so the token
+/// travels as a query parameter. Returns whether the channel is connected.
+#[hook]
+pub fn use_todo_events(token: Option<String>, on_event: Callback<TodoEvent>) -> bool {
+    let connected = use_state(|| false);
+    // the socket outlives renders, so it reads the latest callback through this cell
+    let latest = use_mut_ref(|| on_event.clone());
+    *latest.borrow_mut() = on_event;
+
+    {
+        let connected = connected.clone();
+        let latest = latest.clone();
+        use_effect_with(token, move |token| {
+            let channel = token.as_ref().map(|token| {
+                let channel = Rc::new(RefCell::new(LiveChannel {
+                    url: format!("{}?token={}", ws_url(LIVE_PATH), token),
+                    on_event: Callback::from(move |event| latest.borrow().emit(event)),
+                    on_status: Callback::from(move |status| connected.set(status)),
+                    connection: None,
+                    retry: None,
+                    attempt: 0,
+                    stopped: false,
+                }));
+                connect(&channel);
+                channel
+            });
+            move || {
+                if let Some(channel) = channel {
+                    let mut live = channel.borrow_mut();
+                    live.stopped = true;
+                    live.retry = None;
+                    live.on_status.emit(false);
+                    live.connection = None;
+                }
+            }
+        });
+    }
+
+    *connected
+}
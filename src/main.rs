@@ -1,25 +1,28 @@
-use jsonwebtoken::dangerous::insecure_decode;
-use web_sys::console::log_1;
-use web_sys::window;
 use yew::prelude::*;
 use yew_router::prelude::*;
+mod api;
 mod context;
 mod components;
 mod containers;
+mod hooks;
 mod screens;
 mod types;
 mod utils;  
 use crate::context::auth::AuthProvider;
+use crate::screens::agenda::AgendaScreen;
+use crate::screens::board::BoardScreen;
+use crate::screens::calendar::CalendarScreen;
 use crate::screens::counter::Counter;
 use crate::screens::home::Home;
 use crate::screens::not_found::NotFound;
 use crate::screens::register::RegisterScreen;
 use crate::screens::todo::TodoScreen;
 use crate::components::header::{ Header};
+use crate::components::protected_route::ProtectedRoute;
 use crate::screens::login::LoginScreen;
 
 #[derive(Clone, Routable, PartialEq)]
-enum Route {
+pub enum Route {
     #[at("/")]
     Counter,
     #[at("/*path")]
@@ -28,47 +31,49 @@ enum Route {
     Home,
     #[at("/todo")]
     Todo,
+    #[at("/lists/:id")]
+    List { id: i64 },
+    #[at("/board")]
+    Board,
+    #[at("/lists/:id/board")]
+    ListBoard { id: i64 },
+    #[at("/calendar")]
+    Calendar,
+    #[at("/lists/:id/calendar")]
+    ListCalendar { id: i64 },
+    #[at("/agenda")]
+    Agenda,
+    #[at("/lists/:id/agenda")]
+    ListAgenda { id: i64 },
     #[at("/login")]
     Login,
     #[at("/register")]
     Register,
 }
-fn is_logged_in() -> bool {
-    //check in local storage or context
-    if let Ok(Some(storage)) = window().unwrap().local_storage() {
-        let auth_token = storage.get_item("auth_token").unwrap();
-        return match auth_token {
-            Some(_) => true,
-            None => false,
-        };
-    }
-    false
-}
 #[function_component]
 fn App() -> Html {
-    let on_login = Callback::from(|e:MouseEvent| {
-    //       let mut button_text = "Login".to_string();
-
-    // if let Ok(Some(storage)) = window().unwrap().local_storage() {
-    //     if let Ok(Some(token)) = storage.get_item("auth_token") {
-    //         if let Ok(data) = insecure_decode::<Claims>(&token) {
-    //             if let Some(user_id) = data.claims.sub {
-    //                 button_text = user_id;
-    //             }
-    //         } else {
-    //             web_sys::console::log_1(&"Failed to decode token".into());
-    //         }
-    //     }
-    // }
-
-    });
-
     fn switch(routes: Route) -> Html {
         match routes {
             Route::Counter => html! { <Redirect<Route> to={Route::Todo} /> },
             Route::NotFound => html! { <NotFound /> },
             Route::Home => html! { <Redirect<Route> to={Route::Todo} /> },
-            Route::Todo => html! { <TodoScreen />    },
+            Route::Todo => html! { <ProtectedRoute><TodoScreen /></ProtectedRoute> },
+            // keyed so switching lists starts from a fresh screen state
+            Route::List { id } => html! {
+                <ProtectedRoute><TodoScreen key={id} list_id={Some(id)} /></ProtectedRoute>
+            },
+            Route::Board => html! { <ProtectedRoute><BoardScreen /></ProtectedRoute> },
+            Route::ListBoard { id } => html! {
+                <ProtectedRoute><BoardScreen key={id} list_id={Some(id)} /></ProtectedRoute>
+            },
+            Route::Calendar => html! { <ProtectedRoute><CalendarScreen /></ProtectedRoute> },
+            Route::ListCalendar { id } => html! {
+                <ProtectedRoute><CalendarScreen key={id} list_id={Some(id)} /></ProtectedRoute>
+            },
+            Route::Agenda => html! { <ProtectedRoute><AgendaScreen /></ProtectedRoute> },
+            Route::ListAgenda { id } => html! {
+                <ProtectedRoute><AgendaScreen key={id} list_id={Some(id)} /></ProtectedRoute>
+            },
             Route::Login => html! { <LoginScreen /> },
             Route::Register => html! { <RegisterScreen /> },
         }
@@ -76,10 +81,12 @@ fn App() -> Html {
     }
 
     html! {
+        <AuthProvider>
             <BrowserRouter>
                 <Header class="bg-gray-900 p-4" disabled={false} />
                 <Switch<Route> render={switch} />
             </BrowserRouter>
+        </AuthProvider>
     }
 }
 
@@ -0,0 +1,124 @@
+use chrono::Days;
+use yew::{Callback, Html, Properties, function_component, html};
+
+use crate::api::models::TodoResponse;
+use crate::components::todo_fields::{priority_badge_class, status_badge_class};
+use crate::components::toast::Toast;
+use crate::components::view_tabs::{TodoView, ViewTabs};
+use crate::hooks::use_all_todos::{use_all_todos, AllTodos};
+use crate::utils::dates::{format_relative, format_timestamp, group_by_due, today, AgendaGroup};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct AgendaScreenProps {
+    /// Shows only this list; `None` shows every todo.
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+}
+
+/// Todos with a due date grouped into Overdue, Today and Upcoming, then by day.
+#[function_component(AgendaScreen)]
+pub fn agenda(props: &AgendaScreenProps) -> Html {
+    let all = use_all_todos(props.list_id);
+    let today = today();
+    let tomorrow = today + Days::new(1);
+
+    let handle_dismiss_error = {
+        let all = all.clone();
+        Callback::from(move |_| {
+            all.set(AllTodos {
+                error: None,
+                ..(*all).clone()
+            })
+        })
+    };
+
+    let days = group_by_due(&all.items);
+    let day_label = |day: chrono::NaiveDate| {
+        if day == today {
+            "Today".to_string()
+        } else if day == tomorrow {
+            "Tomorrow".to_string()
+        } else {
+            day.format("%A, %b %-d").to_string()
+        }
+    };
+
+    let render_todo = |todo: &TodoResponse| {
+        html! {
+            <li key={todo.id} class="flex items-center gap-3 p-3 bg-gray-800 rounded-lg">
+                <span class={format!("px-2 py-0.5 rounded text-xs {}", status_badge_class(todo.status()))}>
+                    {todo.status().label()}
+                </span>
+                <span class={if todo.completed { "flex-1 text-gray-400 line-through" } else { "flex-1 text-white" }}>
+                    {&todo.title}
+                </span>
+                <span class={format!("px-2 py-0.5 rounded text-xs {}", priority_badge_class(todo.priority))}>
+                    {todo.priority.label()}
+                </span>
+                <span class="text-xs text-gray-500" title={format!("Created {}", format_timestamp(&todo.created_at))}>
+                    {format!("updated {}", format_relative(&todo.updated_at))}
+                </span>
+            </li>
+        }
+    };
+
+    let render_group = |group: &AgendaGroup| {
+        let group = *group;
+        let in_group: Vec<_> = days
+            .iter()
+            .filter_map(|(day, todos)| {
+                let todos: Vec<&TodoResponse> = todos
+                    .iter()
+                    .filter(|todo| AgendaGroup::of(todo, *day, today) == Some(group))
+                    .collect();
+                (!todos.is_empty()).then_some((*day, todos))
+            })
+            .collect();
+        let count: usize = in_group.iter().map(|(_, todos)| todos.len()).sum();
+
+        html! {
+            <section key={group.label()} class="mb-8">
+                <h2 class={format!("mb-3 text-lg font-semibold {}",
+                    if group == AgendaGroup::Overdue && count > 0 { "text-red-400" } else { "text-white" })}
+                >
+                    {format!("{} ({})", group.label(), count)}
+                </h2>
+                if in_group.is_empty() {
+                    <p class="text-sm text-gray-500">{"Nothing here."}</p>
+                }
+                { for in_group.iter().map(|(day, todos)| html! {
+                    <div key={day.to_string()} class="mb-4">
+                        // today's group already says which day it is
+                        if group != AgendaGroup::Today {
+                            <h3 class="mb-2 text-sm text-gray-400">{day_label(*day)}</h3>
+                        }
+                        <ul class="space-y-2">
+                            { for todos.iter().map(|todo| render_todo(todo)) }
+                        </ul>
+                    </div>
+                }) }
+            </section>
+        }
+    };
+
+    html! {
+        <div class="min-h-screen py-8 bg-gray-900">
+            <div class="max-w-4xl px-4 mx-auto">
+                <div class="flex flex-wrap items-center justify-between gap-4 mb-6">
+                    <h1 class="text-3xl font-bold text-white">{"Agenda"}</h1>
+                    <ViewTabs current={TodoView::Agenda} list_id={props.list_id} />
+                </div>
+
+                if let Some(err) = &all.error {
+                    <Toast message={err.clone()} ondismiss={handle_dismiss_error} />
+                }
+
+                if all.loading && all.items.is_empty() {
+                    <p class="py-12 text-center text-gray-400">{"Loading..."}</p>
+                } else {
+                    { for AgendaGroup::ALL.iter().map(render_group) }
+                }
+            </div>
+        </div>
+    }
+}
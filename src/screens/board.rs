@@ -0,0 +1,408 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::DragEvent;
+use yew::{Callback, Html, Properties, function_component, html, use_effect_with, use_state};
+
+use crate::api::models::{TodoResponse, UpdateTodoType};
+use crate::components::load_more::LoadMore;
+use crate::components::toast::Toast;
+use crate::components::todo_fields::priority_badge_class;
+use crate::components::view_tabs::{TodoView, ViewTabs};
+use crate::containers::todo_list::TodoListAction;
+use crate::context::auth::use_auth;
+use crate::hooks::use_online::use_online;
+use crate::hooks::use_paged_todos::{use_paged_todos, PagedTodos};
+use crate::hooks::use_report_error::use_report_error;
+use crate::types::Status;
+use crate::utils::dates::today;
+use crate::utils::storage::{load_item, store_item};
+use crate::utils::todo_query::TodoQuery;
+
+const COLUMNS_KEY: &str = "board_columns";
+
+/// Visible columns in display order, e.g. `PENDING,IN_PROGRESS,DONE`.
+fn load_columns() -> Vec<Status> {
+    let mut columns: Vec<Status> = Vec::new();
+    for status in load_item(COLUMNS_KEY).unwrap_or_default().split(',') {
+        let status = status.trim();
+        if Status::ALL.iter().any(|s| s.as_str() == status) {
+            let status = Status::from_str(status);
+            if !columns.contains(&status) {
+                columns.push(status);
+            }
+        }
+    }
+    if columns.is_empty() {
+        Status::ALL.to_vec()
+    } else {
+        columns
+    }
+}
+
+fn store_columns(columns: &[Status]) {
+    let value: Vec<&str> = columns.iter().map(|status| status.as_str()).collect();
+    store_item(COLUMNS_KEY, Some(&value.join(",")));
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct BoardScreenProps {
+    /// Shows only this list; `None` shows every todo.
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+}
+
+/// Kanban board: one column per status, dragging a card changes its status.
+#[function_component(BoardScreen)]
+pub fn board(props: &BoardScreenProps) -> Html {
+    let list_id = props.list_id;
+    let auth = use_auth();
+    let client = auth.api_client();
+    let error = use_state(|| Option::<String>::None);
+    let report_error = use_report_error(error.clone());
+    // starts from what the list view cached for the same user and list
+    let PagedTodos {
+        todos,
+        loading,
+        loading_more,
+        reload,
+        load_more: handle_load_more,
+        ..
+    } = use_paged_todos(list_id, report_error.clone());
+    let columns = use_state(load_columns);
+    let show_settings = use_state(|| false);
+    let dragging = use_state(|| Option::<i64>::None);
+    let drop_target = use_state(|| Option::<Status>::None);
+    let online = use_online();
+
+    use_effect_with((auth.is_logged_in(), online), move |(_, online)| {
+        if *online {
+            reload.emit(());
+        }
+        || ()
+    });
+
+    // moves a card to another column, reverting if the backend refuses
+    let handle_move = {
+        let todos = todos.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+        Callback::from(move |(todo, status): (TodoResponse, Status)| {
+            if todo.status() == status {
+                return;
+            }
+            // recurring todos dropped on Done move on to their next occurrence
+            let update = if status == Status::DONE {
+                todo.completion_update(today())
+            } else {
+                UpdateTodoType {
+                    status: Some(status),
+                    completed: Some(false),
+                    ..todo.to_update()
+                }
+            };
+            todos.dispatch(TodoListAction::Upsert(todo.patched(&update)));
+
+            let todos = todos.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+            spawn_local(async move {
+                match client.update_todo(&update).await {
+                    Ok(Some(saved)) => todos.dispatch(TodoListAction::Upsert(saved)),
+                    Ok(None) => {}
+                    Err(err) => {
+                        todos.dispatch(TodoListAction::Upsert(todo));
+                        report_error.emit(("Could not move todo.", err));
+                    }
+                }
+            });
+        })
+    };
+
+    let handle_drag_end = {
+        let dragging = dragging.clone();
+        let drop_target = drop_target.clone();
+        Callback::from(move |_: DragEvent| {
+            dragging.set(None);
+            drop_target.set(None);
+        })
+    };
+
+    let update_columns = {
+        let columns = columns.clone();
+        Callback::from(move |next: Vec<Status>| {
+            // at least one column stays visible
+            if !next.is_empty() {
+                store_columns(&next);
+                columns.set(next);
+            }
+        })
+    };
+
+    let handle_toggle_settings = {
+        let show_settings = show_settings.clone();
+        Callback::from(move |_| show_settings.set(!*show_settings))
+    };
+
+    let handle_dismiss_error = {
+        let error = error.clone();
+        Callback::from(move |_| error.set(None))
+    };
+
+    // grouped in manual order, like the list view
+    let ordered = TodoQuery::default().apply(&todos.items);
+    let can_move = online;
+    let count_of = |status: Status| ordered.iter().filter(|todo| todo.status() == status).count();
+    // todos whose column is turned off would otherwise vanish without a trace
+    let hidden: Vec<(Status, usize)> = Status::ALL
+        .iter()
+        .filter(|status| !columns.contains(status))
+        .map(|status| (*status, count_of(*status)))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    let render_card = |todo: &TodoResponse, index: usize| {
+        let status = todo.status();
+        let step = |offset: isize| {
+            let target = index
+                .checked_add_signed(offset)
+                .and_then(|i| columns.get(i).copied());
+            let handle_move = handle_move.clone();
+            let todo = todo.clone();
+            (
+                target.is_some(),
+                Callback::from(move |_| {
+                    if let Some(target) = target {
+                        handle_move.emit((todo.clone(), target));
+                    }
+                }),
+            )
+        };
+        let (has_left, move_left) = step(-1);
+        let (has_right, move_right) = step(1);
+        let drag_start = {
+            let dragging = dragging.clone();
+            let todo_id = todo.id;
+            Callback::from(move |e: DragEvent| {
+                // Firefox only starts a drag when some data is set
+                if let Some(data) = e.data_transfer() {
+                    let _ = data.set_data("text/plain", &todo_id.to_string());
+                }
+                dragging.set(Some(todo_id));
+            })
+        };
+
+        html! {
+            <div
+                key={todo.id}
+                draggable={can_move.to_string()}
+                ondragstart={drag_start}
+                ondragend={handle_drag_end.clone()}
+                class={classes_for_card(*dragging == Some(todo.id), can_move)}
+            >
+                <p class={if status == Status::DONE { "text-gray-400 line-through" } else { "text-white" }}>
+                    {&todo.title}
+                </p>
+                <div class="flex flex-wrap items-center gap-2 mt-2 text-xs">
+                    <span class={format!("px-2 py-0.5 rounded {}", priority_badge_class(todo.priority))}>
+                        {todo.priority.label()}
+                    </span>
+                    if let Some(due) = todo.due() {
+                        <span class="text-gray-400">{format!("📅 {}", due.format("%b %-d"))}</span>
+                    }
+                    { for todo.tags.iter().map(|tag| html! {
+                        <span class="text-blue-300">{format!("#{}", tag)}</span>
+                    }) }
+                    <div class="flex gap-1 ml-auto">
+                        <button
+                            onclick={move_left}
+                            disabled={!can_move || !has_left}
+                            class="px-1 text-gray-400 hover:text-white disabled:opacity-30"
+                            title="Move to previous column"
+                        >
+                            {"←"}
+                        </button>
+                        <button
+                            onclick={move_right}
+                            disabled={!can_move || !has_right}
+                            class="px-1 text-gray-400 hover:text-white disabled:opacity-30"
+                            title="Move to next column"
+                        >
+                            {"→"}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        }
+    };
+
+    let render_column = |(index, status): (usize, &Status)| {
+        let status = *status;
+        let cards: Vec<&TodoResponse> =
+            ordered.iter().filter(|todo| todo.status() == status).collect();
+        let drag_over = {
+            let drop_target = drop_target.clone();
+            let is_dragging = dragging.is_some();
+            Callback::from(move |e: DragEvent| {
+                if is_dragging {
+                    e.prevent_default();
+                    if *drop_target != Some(status) {
+                        drop_target.set(Some(status));
+                    }
+                }
+            })
+        };
+        let drop = {
+            let dragged = dragging.and_then(|id| todos.items.iter().find(|todo| todo.id == id).cloned());
+            let handle_move = handle_move.clone();
+            let dragging = dragging.clone();
+            let drop_target = drop_target.clone();
+            Callback::from(move |e: DragEvent| {
+                e.prevent_default();
+                if let Some(todo) = dragged.clone() {
+                    handle_move.emit((todo, status));
+                }
+                dragging.set(None);
+                drop_target.set(None);
+            })
+        };
+        let is_target = *drop_target == Some(status);
+
+        html! {
+            <section
+                key={status.as_str()}
+                ondragover={drag_over}
+                ondrop={drop}
+                class={format!("flex flex-col flex-1 min-w-[16rem] p-3 rounded-lg bg-gray-800 {}",
+                    if is_target { "ring-2 ring-blue-500" } else { "" })}
+            >
+                <h2 class="flex items-center justify-between mb-3 text-sm font-semibold text-gray-300">
+                    {status.label()}
+                    <span class="text-xs font-normal text-gray-500">{cards.len()}</span>
+                </h2>
+                <div class="flex-1 space-y-2">
+                    { for cards.into_iter().map(|todo| render_card(todo, index)) }
+                </div>
+            </section>
+        }
+    };
+
+    let render_setting = |status: &Status| {
+        let status = *status;
+        let position = columns.iter().position(|s| *s == status);
+        let toggle = {
+            let columns = (*columns).clone();
+            let update_columns = update_columns.clone();
+            Callback::from(move |_| {
+                let mut next = columns.clone();
+                match position {
+                    Some(index) => {
+                        next.remove(index);
+                    }
+                    None => next.push(status),
+                }
+                update_columns.emit(next);
+            })
+        };
+        let shift = |offset: isize| {
+            let columns = (*columns).clone();
+            let update_columns = update_columns.clone();
+            let target = position.and_then(|i| i.checked_add_signed(offset)).filter(|i| *i < columns.len());
+            (
+                target.is_some(),
+                Callback::from(move |_| {
+                    if let (Some(from), Some(to)) = (position, target) {
+                        let mut next = columns.clone();
+                        next.swap(from, to);
+                        update_columns.emit(next);
+                    }
+                }),
+            )
+        };
+        let (has_left, shift_left) = shift(-1);
+        let (has_right, shift_right) = shift(1);
+
+        html! {
+            <div key={status.as_str()} class="flex items-center gap-2 text-sm text-gray-300">
+                <input
+                    type="checkbox"
+                    checked={position.is_some()}
+                    disabled={position.is_some() && columns.len() == 1}
+                    onclick={toggle}
+                    class="accent-blue-500"
+                />
+                <span class="flex-1">{status.label()}</span>
+                <span class="text-xs text-gray-500">{count_of(status)}</span>
+                <button onclick={shift_left} disabled={!has_left} class="px-1 text-gray-400 hover:text-white disabled:opacity-30" title="Move column left">{"←"}</button>
+                <button onclick={shift_right} disabled={!has_right} class="px-1 text-gray-400 hover:text-white disabled:opacity-30" title="Move column right">{"→"}</button>
+            </div>
+        }
+    };
+
+    html! {
+        <div class="min-h-screen py-8 bg-gray-900">
+            <div class="px-4 mx-auto max-w-7xl">
+                <div class="flex items-center justify-between mb-6">
+                    <h1 class="text-3xl font-bold text-white">{"Board"}</h1>
+                    <div class="flex items-center gap-4 text-sm">
+                        <button onclick={handle_toggle_settings.clone()} class="text-gray-300 hover:text-white">
+                            {"Columns ▾"}
+                        </button>
+                        <ViewTabs current={TodoView::Board} list_id={list_id} />
+                    </div>
+                </div>
+
+                if *show_settings {
+                    // listed in board order, hidden statuses last
+                    <div class="p-4 mb-6 space-y-2 bg-gray-800 rounded-lg shadow-lg max-w-xs">
+                        { for columns.iter().chain(Status::ALL.iter().filter(|s| !columns.contains(s))).map(render_setting) }
+                    </div>
+                }
+
+                if let Some(err) = &*error {
+                    <Toast message={err.clone()} ondismiss={handle_dismiss_error} />
+                }
+
+                if !online {
+                    <div class="px-4 py-3 mb-6 text-sm text-yellow-200 border border-yellow-600 rounded-lg bg-yellow-900/40">
+                        {"You're offline. Cards can be moved again once the connection is back."}
+                    </div>
+                }
+
+                if todos.items.is_empty() && *loading {
+                    <p class="py-12 text-center text-gray-400">{"Loading..."}</p>
+                } else {
+                    <div class="flex gap-4 overflow-x-auto">
+                        { for columns.iter().enumerate().map(render_column) }
+                    </div>
+                    if !hidden.is_empty() {
+                        <p class="mt-4 text-sm text-gray-400">
+                            {format!(
+                                "{} todo(s) are in hidden columns: {}.",
+                                hidden.iter().map(|(_, count)| count).sum::<usize>(),
+                                hidden
+                                    .iter()
+                                    .map(|(status, count)| format!("{} ({})", status.label(), count))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            )}
+                            <button onclick={handle_toggle_settings} class="ml-2 text-blue-400 hover:underline">
+                                {"Choose columns"}
+                            </button>
+                        </p>
+                    }
+                }
+
+                if todos.has_more {
+                    <LoadMore onload={handle_load_more} disabled={*loading_more} />
+                }
+            </div>
+        </div>
+    }
+}
+
+fn classes_for_card(is_dragged: bool, can_move: bool) -> String {
+    format!(
+        "p-3 bg-gray-700 rounded-lg shadow-md {} {}",
+        if can_move { "cursor-grab" } else { "" },
+        if is_dragged { "opacity-50" } else { "" },
+    )
+}
@@ -0,0 +1,201 @@
+use chrono::{Datelike, Days, NaiveDate};
+use yew::{Callback, Html, Properties, function_component, html, use_state};
+
+use crate::api::models::TodoResponse;
+use crate::components::todo_fields::priority_badge_class;
+use crate::components::toast::Toast;
+use crate::components::view_tabs::{TodoView, ViewTabs};
+use crate::hooks::use_all_todos::{use_all_todos, AllTodos};
+use crate::utils::dates::{add_months, group_by_due, month_grid, today, week_days};
+use crate::utils::storage::{load_item, store_item};
+
+const MODE_KEY: &str = "calendar_mode";
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+/// Titles shown per day in the month grid before "+n more".
+const MONTH_CELL_LIMIT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CalendarMode {
+    Month,
+    Week,
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct CalendarScreenProps {
+    /// Shows only this list; `None` shows every todo.
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+}
+
+/// Month or week grid of todos placed on their due dates.
+#[function_component(CalendarScreen)]
+pub fn calendar(props: &CalendarScreenProps) -> Html {
+    let all = use_all_todos(props.list_id);
+    let mode = use_state(|| match load_item(MODE_KEY).as_deref() {
+        Some("week") => CalendarMode::Week,
+        _ => CalendarMode::Month,
+    });
+    // any day inside the shown month or week
+    let anchor = use_state(today);
+    let today = today();
+
+    let set_mode = |next: CalendarMode| {
+        let mode = mode.clone();
+        Callback::from(move |_| {
+            store_item(
+                MODE_KEY,
+                Some(if next == CalendarMode::Week { "week" } else { "month" }),
+            );
+            mode.set(next);
+        })
+    };
+
+    let step = |forward: bool| {
+        let anchor = anchor.clone();
+        let mode = *mode;
+        Callback::from(move |_| {
+            let next = match (mode, forward) {
+                (CalendarMode::Month, true) => add_months(*anchor, 1),
+                (CalendarMode::Month, false) => add_months(*anchor, -1),
+                (CalendarMode::Week, true) => *anchor + Days::new(7),
+                (CalendarMode::Week, false) => *anchor - Days::new(7),
+            };
+            anchor.set(next);
+        })
+    };
+
+    let go_today = {
+        let anchor = anchor.clone();
+        Callback::from(move |_| anchor.set(today))
+    };
+
+    let handle_dismiss_error = {
+        let all = all.clone();
+        Callback::from(move |_| {
+            all.set(AllTodos {
+                error: None,
+                ..(*all).clone()
+            })
+        })
+    };
+
+    let by_day = group_by_due(&all.items);
+    let todos_on = |day: NaiveDate| -> &[TodoResponse] {
+        by_day
+            .iter()
+            .find(|(due, _)| *due == day)
+            .map(|(_, todos)| todos.as_slice())
+            .unwrap_or(&[])
+    };
+    let undated = all.items.iter().filter(|todo| todo.due().is_none()).count();
+
+    let (days, title) = match *mode {
+        CalendarMode::Month => (month_grid(*anchor), anchor.format("%B %Y").to_string()),
+        CalendarMode::Week => {
+            let days = week_days(*anchor);
+            let title = format!(
+                "{} – {}",
+                days[0].format("%b %-d"),
+                days[6].format("%b %-d, %Y")
+            );
+            (days, title)
+        }
+    };
+
+    let render_todo = |todo: &TodoResponse| {
+        let overdue = !todo.completed && todo.due().is_some_and(|due| due < today);
+        html! {
+            <li
+                key={todo.id}
+                title={todo.title.clone()}
+                class={format!("truncate px-1 rounded text-xs {} {}",
+                    priority_badge_class(todo.priority),
+                    if todo.completed { "line-through opacity-60" } else if overdue { "ring-1 ring-red-500" } else { "" },
+                )}
+            >
+                {&todo.title}
+            </li>
+        }
+    };
+
+    let render_day = |day: &NaiveDate| {
+        let day = *day;
+        let todos = todos_on(day);
+        let in_month = *mode == CalendarMode::Week || day.month() == anchor.month();
+        let limit = match *mode {
+            CalendarMode::Month => MONTH_CELL_LIMIT,
+            CalendarMode::Week => usize::MAX,
+        };
+        html! {
+            <div
+                key={day.to_string()}
+                class={format!("p-2 bg-gray-800 rounded {} {}",
+                    if *mode == CalendarMode::Week { "min-h-[16rem]" } else { "min-h-[6rem]" },
+                    if in_month { "" } else { "opacity-40" },
+                )}
+            >
+                <div class={format!("mb-1 text-xs font-semibold {}",
+                    if day == today { "text-blue-400" } else { "text-gray-400" })}
+                >
+                    {day.day()}
+                </div>
+                <ul class="space-y-1">
+                    { for todos.iter().take(limit).map(render_todo) }
+                </ul>
+                if todos.len() > limit {
+                    <div class="mt-1 text-xs text-gray-500">{format!("+{} more", todos.len() - limit)}</div>
+                }
+            </div>
+        }
+    };
+
+    let mode_class = |value: CalendarMode| {
+        if *mode == value {
+            "px-3 py-1 text-white bg-gray-700 rounded"
+        } else {
+            "px-3 py-1 text-gray-400 rounded hover:text-white"
+        }
+    };
+
+    html! {
+        <div class="min-h-screen py-8 bg-gray-900">
+            <div class="max-w-6xl px-4 mx-auto">
+                <div class="flex flex-wrap items-center justify-between gap-4 mb-6">
+                    <h1 class="text-3xl font-bold text-white">{title}</h1>
+                    <ViewTabs current={TodoView::Calendar} list_id={props.list_id} />
+                </div>
+
+                <div class="flex flex-wrap items-center justify-between gap-4 mb-4 text-sm">
+                    <div class="flex gap-2">
+                        <button onclick={step(false)} class="px-3 py-1 text-gray-300 bg-gray-800 rounded hover:bg-gray-700" title="Previous">{"‹"}</button>
+                        <button onclick={go_today} class="px-3 py-1 text-gray-300 bg-gray-800 rounded hover:bg-gray-700">{"Today"}</button>
+                        <button onclick={step(true)} class="px-3 py-1 text-gray-300 bg-gray-800 rounded hover:bg-gray-700" title="Next">{"›"}</button>
+                    </div>
+                    <div class="inline-flex p-1 bg-gray-800 rounded-lg">
+                        <button onclick={set_mode(CalendarMode::Month)} class={mode_class(CalendarMode::Month)}>{"Month"}</button>
+                        <button onclick={set_mode(CalendarMode::Week)} class={mode_class(CalendarMode::Week)}>{"Week"}</button>
+                    </div>
+                </div>
+
+                if let Some(err) = &all.error {
+                    <Toast message={err.clone()} ondismiss={handle_dismiss_error} />
+                }
+
+                <div class="grid grid-cols-7 gap-2">
+                    { for WEEKDAYS.iter().map(|name| html! {
+                        <div class="text-xs font-semibold text-center text-gray-500 uppercase">{*name}</div>
+                    }) }
+                    { for days.iter().map(render_day) }
+                </div>
+
+                <p class="mt-4 text-sm text-gray-500">
+                    if all.loading {
+                        {"Loading..."}
+                    } else if undated > 0 {
+                        {format!("{} todo(s) without a due date are not shown.", undated)}
+                    }
+                </p>
+            </div>
+        </div>
+    }
+}
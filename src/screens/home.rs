@@ -1,8 +1,11 @@
 use crate::{
+    api::{
+        client::PAGE_SIZE,
+        models::{Todo, TodoResponse},
+    },
     components::button::Button,
-    types::{ApiResponse, Todo, TodoResponse},
+    context::auth::use_auth,
 };
-use gloo_net::http::Request;
 use web_sys::{console, HtmlInputElement, InputEvent, SubmitEvent};
 
 use yew::{platform::spawn_local, prelude::*};
@@ -12,33 +15,37 @@ pub fn Home() -> Html {
    let todos: UseStateHandle<Vec<TodoResponse>> = use_state(|| vec![]);
     let loading = use_state(|| false);
     let task_input = use_state(|| String::new());
+    let error = use_state(|| Option::<String>::None);
+    let auth = use_auth();
+    let client = auth.api_client();
 
     let fetch_todos = {
         let todos = todos.clone();
         let loading = loading.clone();
+        let client = client.clone();
+        let error = error.clone();
 
         Callback::from(move |_| {
             let todos = todos.clone();
             let loading = loading.clone();
+            let client = client.clone();
+            let error = error.clone();
 
             // let response =Request::get()
 
             spawn_local(async move {
                 loading.set(true);
-                match Request::get("http://127.0.0.1:8000/todo").send().await {
-                    Ok(response) => {
-                        if let Ok(res) = response.json::<ApiResponse>().await {
-                            console::log_1(
-                                &format!("Fetched {} todos from API", res.data.len()).into(),
-                            );
-                            todos.set(res.data);
-                        }
+                match client.list_todos_page(0, PAGE_SIZE, None).await {
+                    Ok(page) => {
+                        console::log_1(&format!("Fetched {} todos from API", page.items.len()).into());
+                        todos.set(page.items);
                         loading.set(true);
                     }
                     Err(err) => {
                         console::log_1(
-                            &format!("There's an error fetching todos {:?}", err).into(),
+                            &format!("There's an error fetching todos {}", err).into(),
                         );
+                        error.set(Some(err.to_string()));
                         todos.set(vec![]);
                         loading.set(true);
                     }
@@ -68,6 +75,8 @@ pub fn Home() -> Html {
     let handle_add_todo = {
         let task_input = task_input.clone();
         let fetch_todos = fetch_todos.clone();
+        let error = error.clone();
+        let client = client.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
@@ -76,24 +85,25 @@ pub fn Home() -> Html {
             if !input_val.is_empty() {
                 let task_input = task_input.clone();
                 let fetch_todos = fetch_todos.clone();
+                let error = error.clone();
+                let client = client.clone();
 
                 spawn_local(async move {
-                    let new_todo = Todo { task: input_val };
-                    match Request::post("http://127.0.0.1:8000/todo")
-                        .header("Content-Type", "application/json")
-                        .json(&new_todo)
-                    {
-                        Ok(req) => {
-                            if let Ok(res) = req.send().await {
-                                task_input.set(String::new());
-
-                                fetch_todos.emit(());
-                            }
+                    let new_todo = Todo {
+                        title: input_val,
+                        ..Todo::default()
+                    };
+                    match client.create_todo(&new_todo).await {
+                        Ok(_) => {
+                            task_input.set(String::new());
+
+                            fetch_todos.emit(());
                         }
                         Err(err) => {
                             console::log_1(
-                                &format!("There's an error adding todo {:?}", err).into(),
+                                &format!("There's an error adding todo {}", err).into(),
                             );
+                            error.set(Some(err.to_string()));
                         }
                     }
                 });
@@ -115,6 +125,10 @@ pub fn Home() -> Html {
                 </form>
             </div>
 
+            if let Some(err) = &*error {
+                <p class="text-red-500">{ err }</p>
+            }
+
             if todos.is_empty() {
                 // let loading = loading.clone();
                     if *loading  {
@@ -131,7 +145,7 @@ pub fn Home() -> Html {
                         let task =todo.clone();
                         html!(
                             <div>
-                            <p>{task.task}</p>
+                            <p>{task.title}</p>
                             </div>
                         )
                     })
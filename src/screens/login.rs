@@ -1,32 +1,36 @@
-use gloo_net::http::Request;
-use crate::utils::api_url;
+use crate::api::client::TodoApiClient;
+use crate::api::error::ApiError;
+use crate::api::models::LoginRequest;
+use crate::context::auth::use_auth;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{console, HtmlInputElement};
-use yew::{Callback, Html, TargetCast, function_component, html, use_context, use_state};
-use yew_router::prelude::use_navigator;
+use yew::{Callback, Html, TargetCast, function_component, html, use_state};
+use yew_router::prelude::{use_location, use_navigator};
+use yew_router::AnyRoute;
 use yew::events::{InputEvent, SubmitEvent};
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct LoginRequest {
-    pub username: String,
-    pub password: String,
-}
-
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct LoginResponse {
-    pub token: String,
+/// Query string of `Route::Login`; `redirect` is the path to return to after logging in.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct LoginQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
 }
 
 #[function_component(LoginScreen)]
 pub fn login_screen() -> Html {
-    //let auth_ctx = use_context::<AuthCtx>().expect("AuthCtx missing"); // <- lấy token từ context
+    let auth = use_auth();
     let email = use_state(|| String::new());
     let password = use_state(|| String::new());
     let loading = use_state(|| false);
     let error = use_state(|| Option::<String>::None);
     let success = use_state(|| Option::<String>::None);
     let navigator = use_navigator();
+    // only same-origin paths are accepted as a return target
+    let redirect = use_location()
+        .and_then(|location| location.query::<LoginQuery>().ok())
+        .and_then(|query| query.redirect)
+        .filter(|path| path.starts_with('/') && !path.starts_with("//"));
 
     // --- handlers ---
 
@@ -57,6 +61,8 @@ pub fn login_screen() -> Html {
         let error = error.clone();
         let success = success.clone();
         let navigator = navigator.clone();
+        let login = auth.login.clone();
+        let redirect = redirect.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
@@ -77,6 +83,8 @@ pub fn login_screen() -> Html {
             let error = error.clone();
             let success = success.clone();
             let navigator = navigator.clone();
+            let login = login.clone();
+            let redirect = redirect.clone();
 
             spawn_local(async move {
                 let body = LoginRequest {
@@ -84,66 +92,24 @@ pub fn login_screen() -> Html {
                     password: password_val,
                 };
 
-                let login_url = api_url("api/v1/login");
-                match Request::post(&login_url)
-                    .header("Content-Type", "application/json")
-                    .json(&body)
-                {
-                    Ok(req) => match req.send().await {
-                        Ok(resp) => {
-                            if resp.ok() {
-                                match resp.json::<LoginResponse>().await {
-                                    Ok(login_resp) => {
-                                        console::log_1(
-                                            &format!("Login ok, token = {}", login_resp.token).into()
-                                        );
-
-                                        success.set(Some("Login successful!".into()));
-
-                                        if let Ok(Some(storage)) =
-                                            web_sys::window().unwrap().local_storage()
-                                        {
-                                            let _ = storage.set_item("auth_token", &login_resp.token);
-                                        }
-
-                                        if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                                            let _ = storage.set_item("auth_token", &login_resp.token);
-                                        }
-
-                                      
-                                        // Cập nhật token vào context
-
-                                        // rồi navigate
-                                        if let Some(navigator) = navigator {
-                                            navigator.push(&crate::Route::Todo);
-                                        }
-
-                                    }
-                                    Err(e) => {
-                                        console::log_1(
-                                            &format!("Parse login response error: {:?}", e).into()
-                                        );
-                                        error.set(Some("Cannot parse data from server".into()));
-                                    }
-                                }
-                            } else if resp.status() == 401 {
-                                error.set(Some("Username or password is incorrect".into()));
-                            } else {
-                                error.set(Some(
-                                    format!("Login failed, status: {}", resp.status())
-                                ));
+                match TodoApiClient::default().login(&body).await {
+                    Ok(login_resp) => {
+                        success.set(Some("Login successful!".into()));
+                        login.emit(login_resp);
+
+                        if let Some(navigator) = navigator {
+                            match redirect {
+                                Some(path) => navigator.push(&AnyRoute::new(path)),
+                                None => navigator.push(&crate::Route::Todo),
                             }
                         }
-                        Err(e) => {
-                            console::log_1(
-                                &format!("Send login request error: {:?}", e).into()
-                            );
-                            error.set(Some("Cannot connect to server".into()));
-                        }
-                    },
+                    }
+                    Err(ApiError::Unauthorized(_)) => {
+                        error.set(Some("Username or password is incorrect".into()));
+                    }
                     Err(e) => {
-                        console::log_1(&format!("Build login request error: {:?}", e).into());
-                        error.set(Some("Error creating request".into()));
+                        console::log_1(&format!("Login request error: {:?}", e).into());
+                        error.set(Some(e.to_string()));
                     }
                 }
 
@@ -165,6 +131,13 @@ pub fn login_screen() -> Html {
                     </p>
                 </div>
 
+                // session expired
+                if auth.session_expired && error.is_none() {
+                    <div class="mb-4 px-4 py-3 rounded-lg bg-yellow-900/60 border border-yellow-500 text-sm text-yellow-200">
+                        { "Your session has expired. Please log in again." }
+                    </div>
+                }
+
                 // error
                 if let Some(err) = &*error {
                     <div class="mb-4 px-4 py-3 rounded-lg bg-red-900/60 border border-red-500 text-sm text-red-200">
@@ -1,3 +1,6 @@
+pub mod agenda;
+pub mod board;
+pub mod calendar;
 pub mod counter;
 pub mod not_found;
 pub mod my_screen;
@@ -1,17 +1,11 @@
-use gloo_net::http::Request;
-use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{console, HtmlInputElement};
 use yew::{Callback, Html, TargetCast, function_component, html, use_state};
 use yew_router::prelude::use_navigator;
 use yew::events::{InputEvent, SubmitEvent};
-use crate::utils::api_url;
-
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct RegisterRequest {
-    pub username: String,
-    pub password: String,
-}
+use crate::api::client::TodoApiClient;
+use crate::api::error::ApiError;
+use crate::api::models::RegisterRequest;
 
 #[function_component(RegisterScreen)]
 pub fn register_screen() -> Html {
@@ -73,42 +67,27 @@ pub fn register_screen() -> Html {
             let navigator = navigator.clone();
 
             spawn_local(async move {
-                let register_url = api_url("api/v1/register");
                 let body = RegisterRequest {
                     username: email_val,
                     password: password_val,
                 };
 
-                match Request::post(&register_url)
-                    .header("Content-Type", "application/json")
-                    .json(&body)
-                {
-                    Ok(req) => match req.send().await {
-                        Ok(resp) => {
-                            if resp.ok() {
-                                console::log_1(&"Register ok".into());
-                                success.set(Some("Registration successful!".into()));
-
-                                // After a short delay (or immediately) navigate to Login
-                                if let Some(nav) = navigator {
-                                    nav.push(&crate::Route::Login);
-                                }
-                            } else {
-                                error.set(Some(
-                                    format!("Registration failed, status: {}", resp.status())
-                                ));
-                            }
-                        }
-                        Err(e) => {
-                            console::log_1(
-                                &format!("Send register request error: {:?}", e).into()
-                            );
-                            error.set(Some("Unable to reach the server".into()));
+                match TodoApiClient::default().register(&body).await {
+                    Ok(()) => {
+                        console::log_1(&"Register ok".into());
+                        success.set(Some("Registration successful!".into()));
+
+                        // After a short delay (or immediately) navigate to Login
+                        if let Some(nav) = navigator {
+                            nav.push(&crate::Route::Login);
                         }
-                    },
+                    }
+                    Err(ApiError::Conflict(None)) => {
+                        error.set(Some("This username is already taken".into()));
+                    }
                     Err(e) => {
-                        console::log_1(&format!("Build register request error: {:?}", e).into());
-                        error.set(Some("Failed to build the request".into()));
+                        console::log_1(&format!("Register request error: {:?}", e).into());
+                        error.set(Some(e.to_string()));
                     }
                 }
 
@@ -1,94 +1,249 @@
-use gloo_net::http::Request;
-use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
 use wasm_bindgen_futures::spawn_local;
-use web_sys::{HtmlInputElement, InputEvent, SubmitEvent, console};
-use yew::{Callback, Html, TargetCast, function_component, html, use_effect_with, use_state};
-use crate::utils::api_url;
-
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub enum Status {
-    PENDING,
-    DONE,
+use wasm_bindgen::JsCast;
+use web_sys::{DragEvent, FocusEvent, HtmlInputElement, InputEvent, KeyboardEvent, MouseEvent, SubmitEvent, console};
+use yew::{AttrValue, Callback, Html, NodeRef, Properties, TargetCast, function_component, html, use_effect_with, use_mut_ref, use_node_ref, use_state};
+use crate::api::batch::{apply_batch, create_all, BulkAction, TodoBatch};
+use crate::api::error::ApiError;
+use crate::api::sync::{replay, store_cache, PendingChange, SyncQueue};
+use crate::context::auth::use_auth;
+use crate::api::models::{
+    checklist_progress, find_list, ChecklistItem, ListResponse, Todo, TodoEvent, TodoResponse, UpdateTodoType,
+};
+use crate::hooks::use_online::use_online;
+use crate::hooks::use_paged_todos::{use_paged_todos, PagedTodos};
+use crate::hooks::use_report_error::use_report_error;
+use crate::hooks::use_shortcuts::{use_shortcuts, Shortcut};
+use crate::hooks::use_todo_events::use_todo_events;
+use crate::components::checklist::Checklist;
+use crate::components::command_palette::{Command, CommandPalette};
+use crate::components::confirm_dialog::ConfirmDialog;
+use crate::components::todo_fields::{priority_badge_class, status_badge_class, TodoDraft, TodoFields};
+use crate::components::list_sidebar::ListSidebar;
+use crate::components::quick_add_preview::QuickAddPreview;
+use crate::components::load_more::LoadMore;
+use crate::components::migration_import::MigrationImport;
+use crate::components::bulk_bar::BulkBar;
+use crate::components::toast::{Toast, ToastKind};
+use crate::components::view_tabs::{TodoView, ViewTabs};
+use crate::components::todo_toolbar::TodoToolbar;
+use crate::components::transfer_menu::TransferMenu;
+use crate::containers::todo_list::{plan_move, TodoListAction};
+use crate::types::Status;
+use crate::utils::dates::{format_relative, format_timestamp, today};
+use crate::utils::live_updates_enabled;
+use crate::utils::quick_add::QuickAdd;
+use crate::utils::storage::{load_item, store_item};
+use crate::utils::todo_query::{collect_tags, TodoQuery};
+use yew_router::hooks::{use_location, use_navigator};
+
+const AUTO_COMPLETE_KEY: &str = "checklist_auto_complete";
+
+/// The todo to create from the add form: fields typed into the quick-add text
+/// win over the details form. An unknown `@list` stays in the title.
+fn quick_add_todo(draft: &TodoDraft, lists: &[ListResponse], list_id: Option<i64>) -> Todo {
+    let todo = draft.to_new_todo();
+    let parsed = QuickAdd::parse(&draft.title, chrono::Local::now().naive_local());
+    let list = parsed.list.as_deref().map(|name| (name, find_list(lists, name)));
+
+    let mut title = parsed.title.clone();
+    if let Some((name, None)) = list {
+        title = format!("{} @{}", title, name.replace(' ', "-")).trim().to_string();
+    }
+    let mut tags = todo.tags.clone();
+    for tag in &parsed.tags {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    Todo {
+        title,
+        due_date: parsed.due_string().or(todo.due_date.clone()),
+        priority: parsed.priority.unwrap_or(todo.priority),
+        tags,
+        list_id: list.and_then(|(_, found)| found.map(|list| list.id)).or(list_id),
+        ..todo
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct Todo {
-    pub title: String,
-    pub completed: bool,
+/// Focuses the input behind `node`, if it is rendered.
+fn focus_input(node: &NodeRef) {
+    if let Some(input) = node.cast::<HtmlInputElement>() {
+        let _ = input.focus();
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct TodoResponse {
-    pub id: i64,
-    pub title: String,
-    #[serde(default)]
-    pub description: Option<String>,
-    pub completed: bool,
-    pub created_at: String,
-    pub updated_at: String,
+/// Focuses the card of `todo_id`, which also scrolls it into view.
+fn focus_card(todo_id: i64) {
+    let card = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| {
+            document
+                .query_selector(&format!("[data-todo-id=\"{}\"]", todo_id))
+                .ok()
+                .flatten()
+        })
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(card) = card {
+        let _ = card.focus();
+    }
 }
 
+#[derive(Properties, PartialEq, Clone)]
+pub struct TodoScreenProps {
+    /// Shows only this list; `None` shows every todo.
+    #[prop_or_default]
+    pub list_id: Option<i64>,
+}
 
+/// One change of a bulk action, with where the todo was so a failure can be put back.
+#[derive(Clone)]
+struct SentChange {
+    index: usize,
+    original: TodoResponse,
+    /// Taken off the screen: deleted, or moved to another list.
+    removed: bool,
+    change: PendingChange,
+}
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-pub struct UpdateTodoType {
-    pub id: Option<i64>,
-    pub title: Option<String>,
-    pub description: Option<String>,
-        pub completed: Option<bool>,
+/// What the "Undo" toast takes back.
+#[derive(Clone, PartialEq)]
+enum Undo {
+    /// Deletes held back until the toast closes.
+    Delete,
+    /// Changes already saved; undoing saves these earlier versions again.
+    Revert(Vec<TodoResponse>),
 }
 
+#[derive(Clone, PartialEq)]
+struct UndoToast {
+    /// Remounts the toast, restarting its timer, for every new undoable action.
+    seq: u64,
+    message: String,
+    undo: Undo,
+}
 
 #[function_component(TodoScreen)]
-pub fn todo() -> Html {
-    let todos = use_state(|| vec![]);
-    let input_value = use_state(|| String::new());
-    let edit_todo_id = use_state(|| Option::<i64>::None);
-    let edit_todo_text = use_state(|| String::new());
-    let loading = use_state(|| false);
-    let auth_token = if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-        storage.get_item("auth_token").ok().flatten()
-    } else {
-        None
+pub fn todo(props: &TodoScreenProps) -> Html {
+    let list_id = props.list_id;
+    let auth = use_auth();
+    let client = auth.api_client();
+    let error = use_state(|| Option::<String>::None);
+    let report_error = use_report_error(error.clone());
+    // cache and queue are kept per user so a shared device never mixes accounts
+    let PagedTodos {
+        todos,
+        cache_key,
+        loading,
+        loading_more,
+        unreachable,
+        reload,
+        load_more: handle_load_more,
+        load_all: handle_load_all,
+    } = use_paged_todos(list_id, report_error.clone());
+    let queue = {
+        let user = auth.user();
+        use_mut_ref(move || SyncQueue::load(&user))
     };
+    let new_draft = use_state(TodoDraft::default);
+    let show_details = use_state(|| false);
+    let edit_todo_id = use_state(|| Option::<i64>::None);
+    let edit_draft = use_state(TodoDraft::default);
+    let expanded_checklists = use_state(HashSet::<i64>::new);
+    // indexes into the visible list while a card is dragged
+    let dragging = use_state(|| Option::<usize>::None);
+    let drop_target = use_state(|| Option::<usize>::None);
+    let selected = use_state(HashSet::<i64>::new);
+    // last card clicked without shift, where a shift-click range starts
+    let select_anchor = use_state(|| Option::<i64>::None);
+    // completes a todo once every checklist item is done
+    let checklist_auto_complete =
+        use_state(|| load_item(AUTO_COMPLETE_KEY).as_deref() != Some("false"));
+    let next_unsaved_id = use_mut_ref(|| {
+        todos
+            .items
+            .iter()
+            .map(|todo| todo.id)
+            .fold(queue.borrow().min_local_id(), i64::min)
+    });
+    let notice = use_state(|| Option::<String>::None);
+    let undo = use_state(|| Option::<UndoToast>::None);
+    let undo_seq = use_mut_ref(|| 0u64);
+    // deletes waiting for their undo toast to close
+    let held_deletes = use_mut_ref(Vec::<SentChange>::new);
+    let confirm_bulk_delete = use_state(|| false);
+    // card the keyboard shortcuts act on
+    let focused = use_state(|| Option::<i64>::None);
+    let palette_open = use_state(|| false);
+    let new_title_ref = use_node_ref();
+    // lists an `@list` in the quick-add text can name
+    let lists = use_state(Vec::<ListResponse>::new);
+    // bumped after a migration created lists, to reload them
+    let lists_version = use_state(|| 0u32);
+    let search_ref = use_node_ref();
+    let online = use_online();
+    let offline = !online || *unreachable;
+    let navigator = use_navigator();
+    let query = use_location()
+        .and_then(|location| location.query::<TodoQuery>().ok())
+        .unwrap_or_default();
 
+    {
+        use_effect_with(todos.items.clone(), move |items| {
+            store_cache(&cache_key, items);
+            || ()
+        });
+    }
+
+    // replays queued offline changes, then reloads the first page
     let fetch_todos = {
-        let todos = todos.clone();
+        let queue = queue.clone();
         let loading = loading.clone();
+        let unreachable = unreachable.clone();
+        let error = error.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
         Callback::from(move |_| {
-            console::log_1(&"fetch_todos called".into());
-
-            let todos = todos.clone();
+            if queue.borrow().is_replaying() {
+                return;
+            }
+            let queue = queue.clone();
             let loading = loading.clone();
-            let auth_token = if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
-                storage.get_item("auth_token").ok().flatten()
-            } else {
-                None
-            };
+            let unreachable = unreachable.clone();
+            let error = error.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+            let reload = reload.clone();
             loading.set(true);
             spawn_local(async move {
-                console::log_1(&"Fetching todos from API...".into());
-                let todos_url = api_url("api/v1/todo");
-                match Request::get(&todos_url).header("Authorization", format!("Bearer {}", auth_token.unwrap_or_default()).as_str()).send().await {
-                    Ok(response) => match response.json::<Vec<TodoResponse>>().await {
-                        Ok(api_response) => {
-                            console::log_1(
-                                &format!("Fetched {} todos from API", api_response.len())
-                                    .into(),
-                            );
-                            todos.set(api_response);
-                        }
-                        Err(err) => {
-                            console::log_1(
-                                &format!("Failed to parse todos response: {:?}", err).into(),
-                            );
-                            todos.set(vec![]);
-                        }
-                    },
-                    Err(e) => {
-                        console::log_1(&format!("Failed to fetch todos: {:?}", e).into());
-                        todos.set(vec![]);
+                let mut discarded = Vec::new();
+                let mut failed = None;
+                loop {
+                    let changes = queue.borrow_mut().take();
+                    if changes.is_empty() {
+                        break;
+                    }
+                    let report = replay(&client, changes).await;
+                    queue.borrow_mut().finish(&report);
+                    discarded.extend(report.discarded);
+                    if report.error.is_some() {
+                        failed = report.error;
+                        break;
+                    }
+                }
+
+                if !discarded.is_empty() {
+                    error.set(Some(discarded.join(" ")));
+                }
+                match failed {
+                    None => return reload.emit(()),
+                    Some(ApiError::Network(err)) => {
+                        // keep showing the cached list
+                        console::log_1(&format!("Backend unreachable: {}", err).into());
+                        unreachable.set(true);
                     }
+                    Some(err) => report_error.emit(("Could not load todos.", err)),
                 }
                 loading.set(false);
             });
@@ -97,52 +252,114 @@ pub fn todo() -> Html {
 
     {
         let fetch_todos = fetch_todos.clone();
-        use_effect_with((), move |_| {
-            fetch_todos.emit(());
+        use_effect_with((auth.is_logged_in(), online), move |(_, online)| {
+            if *online {
+                fetch_todos.emit(());
+            }
             || ()
         });
     }
 
+    {
+        let lists = lists.clone();
+        let client = client.clone();
+        use_effect_with((online, *lists_version), move |(online, _)| {
+            if *online {
+                spawn_local(async move {
+                    // the sidebar reports list errors; without lists `@name` stays in the title
+                    if let Ok(result) = client.list_lists().await {
+                        lists.set(result);
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    // changes made by other sessions, pushed over the live channel
+    let handle_todo_event = {
+        let todos = todos.clone();
+        let in_view = move |todo: &TodoResponse| list_id.is_none_or(|id| todo.list_id == Some(id));
+        Callback::from(move |event: TodoEvent| match event {
+            TodoEvent::Created { todo } if in_view(&todo) => {
+                todos.dispatch(TodoListAction::Upsert(todo))
+            }
+            TodoEvent::Created { .. } => {}
+            TodoEvent::Updated { todo } if in_view(&todo) => {
+                todos.dispatch(TodoListAction::Refresh(todo))
+            }
+            // moved to another list
+            TodoEvent::Updated { todo } => todos.dispatch(TodoListAction::Remove(todo.id)),
+            TodoEvent::Deleted { id } => todos.dispatch(TodoListAction::Remove(id)),
+        })
+    };
+    let live_token = auth
+        .token
+        .clone()
+        .filter(|_| live_updates_enabled() && auth.is_logged_in() && online);
+    let live = use_todo_events(live_token, handle_todo_event);
+
     let handle_add_todo = {
-        let input_value = input_value.clone();
+        let new_draft = new_draft.clone();
+        let lists = lists.clone();
+        let todos = todos.clone();
+        let next_unsaved_id = next_unsaved_id.clone();
         let fetch_todos = fetch_todos.clone();
-        let loading = loading.clone();
-        let auth_token = auth_token.clone();
+        let queue = queue.clone();
+        let unreachable = unreachable.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
-            let task_value = (*input_value).clone().trim().to_string();
+            let new_todo = quick_add_todo(&new_draft, &lists, list_id);
 
-            if !task_value.is_empty() {
-                let input_value = input_value.clone();
+            if !new_todo.title.is_empty() {
+                let draft = (*new_draft).clone();
+                let new_draft = new_draft.clone();
+                let todos = todos.clone();
                 let fetch_todos = fetch_todos.clone();
-                let loading = loading.clone();
-                let auth_token = auth_token.clone();
+                let queue = queue.clone();
+                let unreachable = unreachable.clone();
+                let client = client.clone();
+                let report_error = report_error.clone();
+
+                // shown right away under a negative id until the backend assigns one
+                let unsaved_id = {
+                    let mut id = next_unsaved_id.borrow_mut();
+                    *id -= 1;
+                    *id
+                };
+                let now = chrono::Utc::now().to_rfc3339();
+                todos.dispatch(TodoListAction::Upsert(TodoResponse::unsaved(
+                    unsaved_id, &new_todo, &now,
+                )));
+                new_draft.set(TodoDraft::default());
 
-                loading.set(true);
+                let change = PendingChange::Create {
+                    local_id: unsaved_id,
+                    todo: new_todo.clone(),
+                };
+                if offline || queue.borrow().is_busy() {
+                    queue.borrow_mut().push(change);
+                    return;
+                }
                 spawn_local(async move {
-                    let new_todo = Todo { title: task_value, completed: false };
-
-                    let create_url = api_url("api/v1/todo");
-                    match Request::post(&create_url)
-                        .header("Content-Type", "application/json")
-                        .header("Authorization", format!("Bearer {}", auth_token.unwrap_or_default()).as_str())
-                        .json(&new_todo)
-                    {
-                        Ok(req) => {
-                            if let Ok(response) = req.send().await {
-                                if response.status() == 201 {
-                                    input_value.set(String::new());
-                                    fetch_todos.emit(());
-                                } else {
-                                    loading.set(false);
-                                }
-                            } else {
-                                loading.set(false);
-                            }
+                    match client.create_todo(&new_todo).await {
+                        Ok(Some(todo)) => todos.dispatch(TodoListAction::Replace(unsaved_id, todo)),
+                        Ok(None) => {
+                            // the backend did not echo the todo, so its id is unknown
+                            todos.dispatch(TodoListAction::Remove(unsaved_id));
+                            fetch_todos.emit(());
                         }
-                        Err(_) => {
-                            loading.set(false);
+                        Err(ApiError::Network(_)) => {
+                            queue.borrow_mut().push(change);
+                            unreachable.set(true);
+                        }
+                        Err(err) => {
+                            todos.dispatch(TodoListAction::Remove(unsaved_id));
+                            new_draft.set(draft);
+                            report_error.emit(("Could not add todo.", err));
                         }
                     }
                 });
@@ -151,220 +368,435 @@ pub fn todo() -> Html {
     };
 
     let handle_input_change = {
-        let input_value = input_value.clone();
+        let new_draft = new_draft.clone();
         Callback::from(move |e: InputEvent| {
             let input: HtmlInputElement = e.target_unchecked_into();
-            input_value.set(input.value());
+            new_draft.set(TodoDraft {
+                title: input.value(),
+                ..(*new_draft).clone()
+            });
         })
     };
 
-    let handle_delete_todo = {
-        let fetch_todos = fetch_todos.clone();
-        let loading = loading.clone();
-        let auth_token = auth_token.clone();
+    let handle_new_fields_change = {
+        let new_draft = new_draft.clone();
+        Callback::from(move |draft: TodoDraft| new_draft.set(draft))
+    };
 
-        Callback::from(move |todo_id: i64| {
-            let auth_token = auth_token.clone();
-            let fetch_todos = fetch_todos.clone();
-            let loading = loading.clone();
+    let handle_toggle_details = {
+        let show_details = show_details.clone();
+        Callback::from(move |_| show_details.set(!*show_details))
+    };
 
-            loading.set(true);
+    // sends one delete, putting the todo back if the backend refuses
+    let send_delete = {
+        let todos = todos.clone();
+        let queue = queue.clone();
+        let unreachable = unreachable.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+
+        Callback::from(move |sent: SentChange| {
+            let todos = todos.clone();
+            let queue = queue.clone();
+            let unreachable = unreachable.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+
+            let SentChange {
+                index,
+                original: removed,
+                change,
+                ..
+            } = sent;
+            let todo_id = removed.id;
+            // negative ids belong to todos still waiting in the queue
+            if offline || todo_id < 0 || queue.borrow().is_busy() {
+                queue.borrow_mut().push(change);
+                return;
+            }
             spawn_local(async move {
-                let delete_url = api_url(&format!("api/v1/todo/{}", todo_id));
-                match Request::delete(&delete_url)
-                    .header("Authorization", format!("Bearer {}", auth_token.clone().unwrap_or_default()).as_str())
-                    .send()
-                    .await
-                {
-                    Ok(response) => {
-                        if response.status() == 200 {
-                            fetch_todos.emit(());
-                        } else {
-                            loading.set(false);
+                match client.delete_todo(todo_id).await {
+                    // already gone on the server, which is what we wanted
+                    Ok(()) | Err(ApiError::NotFound(_)) => {}
+                    Err(ApiError::Network(_)) => {
+                        queue.borrow_mut().push(change);
+                        unreachable.set(true);
+                    }
+                    Err(err) => {
+                        todos.dispatch(TodoListAction::Restore(index, removed));
+                        report_error.emit(("Could not delete todo.", err));
+                    }
+                }
+            });
+        })
+    };
+
+    // sends bulk changes as one batch, queueing those that cannot go out now
+    let send_bulk = {
+        let todos = todos.clone();
+        let queue = queue.clone();
+        let unreachable = unreachable.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+
+        Callback::from(move |(verb, changes, announce): (&'static str, Vec<SentChange>, bool)| {
+            let mut batch = TodoBatch::default();
+            let mut sent: Vec<SentChange> = Vec::new();
+            let mut queued = 0;
+            for item in changes {
+                if offline || item.original.id < 0 || queue.borrow().is_busy() {
+                    queue.borrow_mut().push(item.change);
+                    queued += 1;
+                    continue;
+                }
+                match &item.change {
+                    PendingChange::Update { update, .. } => batch.update.push(update.clone()),
+                    PendingChange::Delete { id, .. } => batch.delete.push(*id),
+                    PendingChange::Create { .. } => {}
+                }
+                sent.push(item);
+            }
+
+            let queued_note = |queued: usize| {
+                if queued > 0 {
+                    format!(" {} change(s) will sync once the backend is reachable.", queued)
+                } else {
+                    String::new()
+                }
+            };
+            if batch.is_empty() {
+                if announce {
+                    notice.set(Some(format!("{} {} todo(s).{}", verb, queued, queued_note(queued))));
+                }
+                return;
+            }
+
+            let todos = todos.clone();
+            let queue = queue.clone();
+            let unreachable = unreachable.clone();
+            let error = error.clone();
+            let notice = notice.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+            spawn_local(async move {
+                let mut report = apply_batch(&client, &batch).await;
+                for saved in report.saved.drain(..) {
+                    todos.dispatch(TodoListAction::Refresh(saved));
+                }
+
+                let mut queued = queued;
+                let mut unauthorized = None;
+                report.failed.retain(|(id, err)| match err {
+                    // kept for the next sync rather than reverted
+                    ApiError::Network(_) => {
+                        if let Some(item) = sent.iter().find(|item| item.original.id == *id) {
+                            queue.borrow_mut().push(item.change.clone());
+                            queued += 1;
                         }
+                        unreachable.set(true);
+                        report.total -= 1;
+                        false
                     }
-                    Err(_) => {
-                        loading.set(false);
+                    ApiError::Unauthorized(_) => {
+                        unauthorized = Some(err.clone());
+                        true
+                    }
+                    _ => true,
+                });
+                // indexes are from before the removals, so restore front to back
+                sent.sort_by_key(|item| item.index);
+                for item in sent {
+                    if report.failed.iter().any(|(id, _)| *id == item.original.id) {
+                        if item.removed {
+                            todos.dispatch(TodoListAction::Restore(item.index, item.original));
+                        } else {
+                            todos.dispatch(TodoListAction::Upsert(item.original));
+                        }
                     }
                 }
+
+                if let Some(err) = unauthorized {
+                    report_error.emit(("Could not apply changes.", err));
+                } else if !report.failed.is_empty() {
+                    error.set(Some(format!("{}{}", report.summary(verb), queued_note(queued))));
+                } else if announce || queued > 0 {
+                    notice.set(Some(format!("{}{}", report.summary(verb), queued_note(queued))));
+                }
             });
         })
     };
 
-    let handle_toggle_status = {
-        let fetch_todos = fetch_todos.clone();
-        let loading = loading.clone();
+    // sends the deletes held back by the undo toast
+    let commit_deletes = {
+        let held_deletes = held_deletes.clone();
+        let send_delete = send_delete.clone();
+        let send_bulk = send_bulk.clone();
+        Callback::from(move |_: ()| {
+            let mut held = std::mem::take(&mut *held_deletes.borrow_mut());
+            match held.len() {
+                0 => {}
+                1 => send_delete.emit(held.remove(0)),
+                _ => send_bulk.emit(("Deleted", held, false)),
+            }
+        })
+    };
+
+    {
+        // deletes still held back are sent when leaving the screen
+        let latest = use_mut_ref(|| commit_deletes.clone());
+        *latest.borrow_mut() = commit_deletes.clone();
+        use_effect_with((), move |_| move || latest.borrow().emit(()))
+    }
+
+    // replaces the undo toast; whatever the previous one held back is final now
+    let show_undo = {
+        let undo = undo.clone();
+        let undo_seq = undo_seq.clone();
+        Callback::from(move |(message, action): (String, Undo)| {
+            let seq = {
+                let mut seq = undo_seq.borrow_mut();
+                *seq += 1;
+                *seq
+            };
+            undo.set(Some(UndoToast {
+                seq,
+                message,
+                undo: action,
+            }));
+        })
+    };
+
+    // removed right away, sent once the undo toast closes
+    let handle_delete_todo = {
         let todos = todos.clone();
-        let auth_token = auth_token.clone();
+        let queue = queue.clone();
+        let held_deletes = held_deletes.clone();
+        let commit_deletes = commit_deletes.clone();
+        let show_undo = show_undo.clone();
 
         Callback::from(move |todo_id: i64| {
-            let auth_token = auth_token.clone();
-            console::log_1(&format!("Toggle status clicked for todo ID: {}", todo_id).into());
+            if queue.borrow().is_unsaved(todo_id) {
+                return;
+            }
+            let Some(index) = todos.items.iter().position(|todo| todo.id == todo_id) else {
+                return;
+            };
+            let removed = todos.items[index].clone();
+            commit_deletes.emit(());
 
-            let fetch_todos = fetch_todos.clone();
-            let loading = loading.clone();
+            todos.dispatch(TodoListAction::Remove(todo_id));
+            held_deletes.borrow_mut().push(SentChange {
+                index,
+                original: removed.clone(),
+                removed: true,
+                change: PendingChange::Delete {
+                    id: todo_id,
+                    title: removed.title.clone(),
+                    base_updated_at: removed.updated_at.clone(),
+                },
+            });
+            show_undo.emit((format!("Deleted \"{}\".", removed.title), Undo::Delete));
+        })
+    };
+
+    let send_update = {
+        let todos = todos.clone();
+        let queue = queue.clone();
+        let unreachable = unreachable.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+
+        // `onrejected` runs after the backend refused the update and it was rolled back
+        Callback::from(move |(current, update_data, onrejected): (TodoResponse, UpdateTodoType, Callback<()>)| {
             let todos = todos.clone();
+            let queue = queue.clone();
+            let unreachable = unreachable.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
 
-            loading.set(true);
+            todos.dispatch(TodoListAction::Upsert(current.patched(&update_data)));
 
+            let change = PendingChange::Update {
+                update: update_data.clone(),
+                base_updated_at: current.updated_at.clone(),
+            };
+            if offline || current.id < 0 || queue.borrow().is_busy() {
+                queue.borrow_mut().push(change);
+                return;
+            }
             spawn_local(async move {
-                let auth_token = auth_token.clone();
-                console::log_1(&"Fetching current todo status...".into());
-                let todo_url = api_url(&format!("api/v1/todo/{}", todo_id));
-                match Request::get(&todo_url).header("Authorization", format!("Bearer {}", auth_token.clone().unwrap_or_default()).as_str())
-                    .send()
-                    .await
-                {
-                    Ok(response) => {
-                        if let Ok(current_todo) = response.json::<TodoResponse>().await {
-                            let new_status = match &current_todo.completed {
-                                false => "DONE".to_string(),
-                                true => "PENDING".to_string(),
-                            };
-
-                            console::log_1(
-                                &format!(
-                                    "Current status: {:?}, updating to: {}",
-                                    current_todo.completed, new_status
-                                )
-                                .into(),
-                            );
-
-                            let update_data = UpdateTodoType {
-                                id: Some(todo_id),
-                                title: Some(current_todo.title),
-                                description: None,
-                                completed: Some(!current_todo.completed),
-
-                            };
-
-                            console::log_1(&"Sending PUT request...".into());
-                            let update_url = api_url("api/v1/todo");
-                            match Request::put(&update_url)
-                                .header("Content-Type", "application/json")
-                                .header("Authorization", format!("Bearer {}", auth_token.unwrap_or_default()).as_str())
-                                .json(&update_data)
-                            {
-                                Ok(req) => match req.send().await {
-                                    Ok(response) => {
-                                        console::log_1(
-                                            &format!("PUT response status: {}", response.status())
-                                                .into(),
-                                        );
-                                        if response.status() == 200 {
-                                            console::log_1(
-                                                &"Status update successful, fetching todos..."
-                                                    .into(),
-                                            );
-                                            fetch_todos.emit(());
-                                        } else {
-                                            console::log_1(
-                                                &"PUT request failed with non-200 status".into(),
-                                            );
-                                            loading.set(false);
-                                        }
-                                    }
-                                    Err(_) => {
-                                        console::log_1(
-                                            &"Failed to get response from PUT request".into(),
-                                        );
-                                        loading.set(false);
-                                    }
-                                },
-                                Err(e) => {
-                                    console::log_1(&format!("PUT request error: {:?}", e).into());
-                                    loading.set(false);
-                                }
-                            }
-                        } else {
-                            console::log_1(&"Failed to parse current todo response".into());
-                            loading.set(false);
-                        }
+                match client.update_todo(&update_data).await {
+                    Ok(Some(saved)) => todos.dispatch(TodoListAction::Upsert(saved)),
+                    Ok(None) => {}
+                    Err(ApiError::Network(_)) => {
+                        queue.borrow_mut().push(change);
+                        unreachable.set(true);
                     }
-                    Err(e) => {
-                        console::log_1(&format!("Failed to fetch current todo: {:?}", e).into());
-                        loading.set(false);
+                    Err(err) => {
+                        todos.dispatch(TodoListAction::Upsert(current));
+                        onrejected.emit(());
+                        report_error.emit(("Could not update todo.", err));
                     }
                 }
             });
         })
     };
 
+    let handle_toggle_status = {
+        let todos = todos.clone();
+        let queue = queue.clone();
+        let send_update = send_update.clone();
+
+        Callback::from(move |todo_id: i64| {
+            if queue.borrow().is_unsaved(todo_id) {
+                return;
+            }
+            if let Some(current) = todos.items.iter().find(|todo| todo.id == todo_id) {
+                // completing a recurring todo moves it on to its next occurrence
+                let update_data = if current.completed {
+                    UpdateTodoType {
+                        completed: Some(false),
+                        status: Some(Status::PENDING),
+                        ..current.to_update()
+                    }
+                } else {
+                    current.completion_update(today())
+                };
+                send_update.emit((current.clone(), update_data, Callback::noop()));
+            }
+        })
+    };
+
+    let handle_checklist_change = {
+        let todos = todos.clone();
+        let queue = queue.clone();
+        let send_update = send_update.clone();
+        let auto_complete = *checklist_auto_complete;
+
+        Callback::from(move |(todo_id, checklist): (i64, Vec<ChecklistItem>)| {
+            if queue.borrow().is_unsaved(todo_id) {
+                return;
+            }
+            if let Some(current) = todos.items.iter().find(|todo| todo.id == todo_id) {
+                let all_done = auto_complete
+                    && checklist_progress(&checklist).is_some_and(|(done, total)| done == total);
+                let update_data = if all_done && !current.completed {
+                    // same as ticking the todo itself, so recurring todos move on
+                    TodoResponse {
+                        checklist,
+                        ..current.clone()
+                    }
+                    .completion_update(today())
+                } else {
+                    let status = if all_done { Status::DONE } else { current.status() };
+                    UpdateTodoType {
+                        completed: Some(status == Status::DONE),
+                        status: Some(status),
+                        checklist: Some(checklist),
+                        ..current.to_update()
+                    }
+                };
+                send_update.emit((current.clone(), update_data, Callback::noop()));
+            }
+        })
+    };
+
+    let handle_toggle_checklist = {
+        let expanded_checklists = expanded_checklists.clone();
+        Callback::from(move |todo_id: i64| {
+            let mut expanded = (*expanded_checklists).clone();
+            if !expanded.remove(&todo_id) {
+                expanded.insert(todo_id);
+            }
+            expanded_checklists.set(expanded);
+        })
+    };
+
+    let handle_auto_complete_change = {
+        let checklist_auto_complete = checklist_auto_complete.clone();
+        Callback::from(move |_| {
+            let enabled = !*checklist_auto_complete;
+            store_item(AUTO_COMPLETE_KEY, Some(if enabled { "true" } else { "false" }));
+            checklist_auto_complete.set(enabled);
+        })
+    };
+
     let handle_edit_click = {
         let edit_todo_id = edit_todo_id.clone();
-        let edit_todo_text = edit_todo_text.clone();
+        let edit_draft = edit_draft.clone();
+        let queue = queue.clone();
 
-        Callback::from(move |(id, text): (i64, String)| {
-            edit_todo_id.set(Some(id));
-            edit_todo_text.set(text);
+        Callback::from(move |todo: TodoResponse| {
+            if queue.borrow().is_unsaved(todo.id) {
+                return;
+            }
+            edit_todo_id.set(Some(todo.id));
+            edit_draft.set(TodoDraft::from_todo(&todo));
         })
     };
 
     let handle_edit_input_change = {
-        let edit_todo_text = edit_todo_text.clone();
+        let edit_draft = edit_draft.clone();
         Callback::from(move |e: InputEvent| {
             let input: HtmlInputElement = e.target_unchecked_into();
-            edit_todo_text.set(input.value());
+            edit_draft.set(TodoDraft {
+                title: input.value(),
+                ..(*edit_draft).clone()
+            });
         })
     };
 
+    let handle_edit_fields_change = {
+        let edit_draft = edit_draft.clone();
+        Callback::from(move |draft: TodoDraft| edit_draft.set(draft))
+    };
+
     let handle_save_edit = {
         let edit_todo_id = edit_todo_id.clone();
-        let edit_todo_text = edit_todo_text.clone();
-        let fetch_todos = fetch_todos.clone();
-        let loading = loading.clone();
+        let edit_draft = edit_draft.clone();
+        let commit_deletes = commit_deletes.clone();
+        let show_undo = show_undo.clone();
+        let undo = undo.clone();
+        let undo_seq = undo_seq.clone();
         let todos = todos.clone();
+        let send_update = send_update.clone();
 
         Callback::from(move |_| {
-            let auth_token = auth_token.clone();
-            if let Some(todo_id) = *edit_todo_id {
-                let new_text = (*edit_todo_text).clone().trim().to_string();
-
-                if !new_text.is_empty() {
-                    let current_completed = todos
-                        .iter()
-                        .find(|todo| todo.id == todo_id)
-                        .map(|todo| todo.completed)
-                        .unwrap_or(false);
-
-                    let edit_todo_id = edit_todo_id.clone();
-                    let fetch_todos = fetch_todos.clone();
-                    let loading = loading.clone();
-
-                    let update_data = UpdateTodoType {
-                        id: Some(todo_id),
-                        title: Some(new_text),
-                        description: None,
-                        completed: Some(current_completed),
-                    };
-
-                    loading.set(true);
-                    spawn_local(async move {
-                        let update_url = api_url("api/v1/todo");
-                        match Request::put(&update_url)
-                            .header("Content-Type", "application/json")
-                            .header("Authorization", format!("Bearer {}", auth_token.clone().unwrap_or_default()).as_str())
-                            .json(&update_data)
-                        {
-                            Ok(req) => {
-                                if let Ok(response) = req.send().await {
-                                    if response.status() == 200 {
-                                        edit_todo_id.set(None);
-                                        fetch_todos.emit(());
-                                    } else {
-                                        loading.set(false);
-                                    }
-                                } else {
-                                    loading.set(false);
-                                }
-                            }
-                            Err(_) => {
-                                loading.set(false);
-                            }
-                        }
-                    });
-                }
+            let Some(todo_id) = *edit_todo_id else {
+                return;
+            };
+            if edit_draft.title.trim().is_empty() {
+                return;
             }
+            let Some(current) = todos.items.iter().find(|todo| todo.id == todo_id).cloned() else {
+                return;
+            };
+
+            let update_data = edit_draft.to_update(todo_id, current.completed);
+            edit_todo_id.set(None);
+            commit_deletes.emit(());
+            show_undo.emit((
+                format!("Saved \"{}\".", update_data.title.clone().unwrap_or_default()),
+                Undo::Revert(vec![current.clone()]),
+            ));
+            let seq = *undo_seq.borrow();
+            // reopen the editor, the draft is still there, and take back the "Saved" toast
+            // unless another one replaced it
+            let reopen = {
+                let edit_todo_id = edit_todo_id.clone();
+                let undo = undo.clone();
+                let undo_seq = undo_seq.clone();
+                Callback::from(move |_| {
+                    edit_todo_id.set(Some(todo_id));
+                    if *undo_seq.borrow() == seq {
+                        undo.set(None);
+                    }
+                })
+            };
+            send_update.emit((current, update_data, reopen));
         })
     };
 
@@ -375,186 +807,1083 @@ pub fn todo() -> Html {
         })
     };
 
+    // the view lives in the URL so it can be shared and survives a reload
+    let handle_query_change = {
+        let navigator = navigator.clone();
+        Callback::from(move |query: TodoQuery| {
+            let route = match list_id {
+                Some(id) => crate::Route::List { id },
+                None => crate::Route::Todo,
+            };
+            if let Some(navigator) = &navigator {
+                if query == TodoQuery::default() {
+                    navigator.replace(&route);
+                } else {
+                    let _ = navigator.replace_with_query(&route, &query);
+                }
+            }
+        })
+    };
+
+    let handle_clear_filters = {
+        let handle_query_change = handle_query_change.clone();
+        Callback::from(move |_| handle_query_change.emit(TodoQuery::default()))
+    };
+
+    let visible_todos = query.apply(&todos.items);
+    let can_reorder = query.allows_reorder() && !offline;
+
+    // moves visible_todos[from] to index `to`, reverting if the backend refuses
+    let handle_move = {
+        let todos = todos.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+        let ordered = visible_todos.clone();
+
+        Callback::from(move |(from, to): (usize, usize)| {
+            let moves = plan_move(&ordered, from, to);
+            if moves.is_empty() {
+                return;
+            }
+            let previous: Vec<(i64, Option<f64>)> = moves
+                .iter()
+                .filter_map(|m| ordered.iter().find(|todo| todo.id == m.id))
+                .map(|todo| (todo.id, todo.position))
+                .collect();
+            todos.dispatch(TodoListAction::Reposition(
+                moves.iter().map(|m| (m.id, Some(m.position))).collect(),
+            ));
+
+            let todos = todos.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+            spawn_local(async move {
+                if let Err(err) = client.reorder_todos(&moves).await {
+                    todos.dispatch(TodoListAction::Reposition(previous));
+                    report_error.emit(("Could not save the new order.", err));
+                }
+            });
+        })
+    };
+
+    let handle_drag_end = {
+        let dragging = dragging.clone();
+        let drop_target = drop_target.clone();
+        Callback::from(move |_: DragEvent| {
+            dragging.set(None);
+            drop_target.set(None);
+        })
+    };
+
+    let visible_ids: Vec<i64> = visible_todos.iter().map(|todo| todo.id).collect();
+    let selected_count = visible_ids.iter().filter(|id| selected.contains(id)).count();
+
+    // click toggles one card, shift-click selects the range from the anchor
+    let handle_select = {
+        let selected = selected.clone();
+        let select_anchor = select_anchor.clone();
+        let visible_ids = visible_ids.clone();
+        Callback::from(move |(id, shift): (i64, bool)| {
+            let mut next = (*selected).clone();
+            let anchor = select_anchor
+                .and_then(|anchor| visible_ids.iter().position(|other| *other == anchor));
+            let index = visible_ids.iter().position(|other| *other == id);
+            match (shift, anchor, index) {
+                (true, Some(from), Some(to)) => {
+                    next.extend(&visible_ids[from.min(to)..=from.max(to)]);
+                }
+                _ => {
+                    if !next.remove(&id) {
+                        next.insert(id);
+                    }
+                    select_anchor.set(Some(id));
+                }
+            }
+            selected.set(next);
+        })
+    };
+
+    let handle_select_all = {
+        let selected = selected.clone();
+        let visible_ids = visible_ids.clone();
+        Callback::from(move |_| selected.set(visible_ids.iter().copied().collect()))
+    };
+
+    let handle_clear_selection = {
+        let selected = selected.clone();
+        Callback::from(move |_| selected.set(HashSet::new()))
+    };
+
+    // applies `action` to every selected visible todo as one batch
+    let run_bulk_action = {
+        let todos = todos.clone();
+        let selected = selected.clone();
+        let held_deletes = held_deletes.clone();
+        let commit_deletes = commit_deletes.clone();
+        let show_undo = show_undo.clone();
+        let send_bulk = send_bulk.clone();
+        let targets: Vec<TodoResponse> = visible_todos
+            .iter()
+            .filter(|todo| selected.contains(&todo.id))
+            .cloned()
+            .collect();
+
+        Callback::from(move |action: BulkAction| {
+            if targets.is_empty() {
+                return;
+            }
+            commit_deletes.emit(());
+
+            let today = today();
+            let mut changes: Vec<SentChange> = Vec::new();
+            for todo in &targets {
+                let index = todos
+                    .items
+                    .iter()
+                    .position(|item| item.id == todo.id)
+                    .unwrap_or(todos.items.len());
+                let (change, removed) = match action.update_for(todo, today) {
+                    Some(update) => {
+                        let patched = todo.patched(&update);
+                        // moved out of the list on screen
+                        let removed = list_id.is_some_and(|id| patched.list_id != Some(id));
+                        if removed {
+                            todos.dispatch(TodoListAction::Remove(todo.id));
+                        } else {
+                            todos.dispatch(TodoListAction::Upsert(patched));
+                        }
+                        let change = PendingChange::Update {
+                            update,
+                            base_updated_at: todo.updated_at.clone(),
+                        };
+                        (change, removed)
+                    }
+                    None => {
+                        todos.dispatch(TodoListAction::Remove(todo.id));
+                        let change = PendingChange::Delete {
+                            id: todo.id,
+                            title: todo.title.clone(),
+                            base_updated_at: todo.updated_at.clone(),
+                        };
+                        (change, true)
+                    }
+                };
+                changes.push(SentChange {
+                    index,
+                    original: todo.clone(),
+                    removed,
+                    change,
+                });
+            }
+            selected.set(HashSet::new());
+
+            let count = changes.len();
+            match action {
+                BulkAction::Delete => {
+                    held_deletes.borrow_mut().extend(changes);
+                    show_undo.emit((format!("Deleted {} todo(s).", count), Undo::Delete));
+                }
+                BulkAction::Complete => {
+                    show_undo.emit((
+                        format!("Completed {} todo(s).", count),
+                        Undo::Revert(targets.clone()),
+                    ));
+                    send_bulk.emit((action.verb(), changes, false));
+                }
+                _ => send_bulk.emit((action.verb(), changes, true)),
+            }
+        })
+    };
+
+    // bulk deletes ask first; everything else runs right away
+    let handle_bulk_action = {
+        let confirm_bulk_delete = confirm_bulk_delete.clone();
+        let run_bulk_action = run_bulk_action.clone();
+        Callback::from(move |action: BulkAction| {
+            if action == BulkAction::Delete {
+                confirm_bulk_delete.set(true);
+            } else {
+                run_bulk_action.emit(action);
+            }
+        })
+    };
+
+    let handle_confirm_bulk_delete = {
+        let confirm_bulk_delete = confirm_bulk_delete.clone();
+        let run_bulk_action = run_bulk_action.clone();
+        Callback::from(move |_| {
+            confirm_bulk_delete.set(false);
+            run_bulk_action.emit(BulkAction::Delete);
+        })
+    };
+
+    let handle_cancel_bulk_delete = {
+        let confirm_bulk_delete = confirm_bulk_delete.clone();
+        Callback::from(move |_| confirm_bulk_delete.set(false))
+    };
+
+    let handle_undo = {
+        let todos = todos.clone();
+        let undo = undo.clone();
+        let held_deletes = held_deletes.clone();
+        let send_update = send_update.clone();
+        let send_bulk = send_bulk.clone();
+        Callback::from(move |_| {
+            let Some(toast) = (*undo).clone() else {
+                return;
+            };
+            undo.set(None);
+            match toast.undo {
+                Undo::Delete => {
+                    let mut held = std::mem::take(&mut *held_deletes.borrow_mut());
+                    // indexes are from before the removals, so restore front to back
+                    held.sort_by_key(|item| item.index);
+                    for item in held {
+                        todos.dispatch(TodoListAction::Restore(item.index, item.original));
+                    }
+                }
+                Undo::Revert(previous) => {
+                    let mut changes: Vec<SentChange> = Vec::new();
+                    for earlier in previous {
+                        let Some(current) = todos.items.iter().find(|todo| todo.id == earlier.id) else {
+                            continue;
+                        };
+                        changes.push(SentChange {
+                            index: 0,
+                            original: current.clone(),
+                            removed: false,
+                            change: PendingChange::Update {
+                                update: earlier.to_update(),
+                                base_updated_at: current.updated_at.clone(),
+                            },
+                        });
+                        todos.dispatch(TodoListAction::Upsert(earlier));
+                    }
+                    if changes.len() == 1 {
+                        let item = changes.remove(0);
+                        if let PendingChange::Update { update, .. } = item.change {
+                            send_update.emit((item.original, update, Callback::noop()));
+                        }
+                    } else if !changes.is_empty() {
+                        send_bulk.emit(("Restored", changes, false));
+                    }
+                }
+            }
+        })
+    };
+
+    let handle_dismiss_undo = {
+        let undo = undo.clone();
+        let commit_deletes = commit_deletes.clone();
+        Callback::from(move |_| {
+            commit_deletes.emit(());
+            undo.set(None);
+        })
+    };
+
+    // the focused card, if it is still shown
+    let focused_todo = focused.and_then(|id| visible_todos.iter().find(|todo| todo.id == id).cloned());
+
+    // moves the focus `delta` cards down the visible list
+    let step_focus = |delta: isize| {
+        let visible_ids = visible_ids.clone();
+        let focused = *focused;
+        Callback::from(move |_| {
+            let position = focused.and_then(|id| visible_ids.iter().position(|other| *other == id));
+            let target = match position {
+                Some(position) => position.saturating_add_signed(delta).min(visible_ids.len().saturating_sub(1)),
+                None if delta < 0 => visible_ids.len().saturating_sub(1),
+                None => 0,
+            };
+            if let Some(id) = visible_ids.get(target) {
+                focus_card(*id);
+            }
+        })
+    };
+
+    let handle_focus_new = {
+        let new_title_ref = new_title_ref.clone();
+        Callback::from(move |_| focus_input(&new_title_ref))
+    };
+
+    let handle_focus_search = {
+        let search_ref = search_ref.clone();
+        Callback::from(move |_| focus_input(&search_ref))
+    };
+
+    let handle_toggle_focused = {
+        let handle_toggle_status = handle_toggle_status.clone();
+        let focused_todo = focused_todo.clone();
+        Callback::from(move |_| {
+            if let Some(todo) = &focused_todo {
+                handle_toggle_status.emit(todo.id);
+            }
+        })
+    };
+
+    let handle_edit_focused = {
+        let handle_edit_click = handle_edit_click.clone();
+        let focused_todo = focused_todo.clone();
+        Callback::from(move |_| {
+            if let Some(todo) = &focused_todo {
+                handle_edit_click.emit(todo.clone());
+            }
+        })
+    };
+
+    let handle_delete_focused = {
+        let handle_delete_todo = handle_delete_todo.clone();
+        let queue = queue.clone();
+        let visible_ids = visible_ids.clone();
+        let focused_todo = focused_todo.clone();
+        Callback::from(move |_| {
+            let Some(todo) = &focused_todo else {
+                return;
+            };
+            if queue.borrow().is_unsaved(todo.id) {
+                return;
+            }
+            // keep the focus in the list: the next card, or the previous one at the end
+            let position = visible_ids.iter().position(|id| *id == todo.id).unwrap_or_default();
+            let neighbour = visible_ids
+                .get(position + 1)
+                .or_else(|| position.checked_sub(1).and_then(|previous| visible_ids.get(previous)))
+                .copied();
+            handle_delete_todo.emit(todo.id);
+            if let Some(id) = neighbour {
+                focus_card(id);
+            }
+        })
+    };
+
+    let handle_escape = {
+        let edit_todo_id = edit_todo_id.clone();
+        Callback::from(move |_| {
+            if let Some(id) = *edit_todo_id {
+                edit_todo_id.set(None);
+                focus_card(id);
+            } else if let Some(active) = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.active_element())
+                .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+            {
+                // leaves the search or new todo field so the plain keys work again
+                let _ = active.blur();
+            }
+        })
+    };
+
+    let handle_toggle_palette = {
+        let palette_open = palette_open.clone();
+        Callback::from(move |_| palette_open.set(!*palette_open))
+    };
+
+    let handle_close_palette = {
+        let palette_open = palette_open.clone();
+        Callback::from(move |_| palette_open.set(false))
+    };
+
+    let shortcuts = vec![
+        Shortcut::new("n", "New todo", handle_focus_new),
+        Shortcut::new("/", "Search todos", handle_focus_search),
+        Shortcut::new("j", "Next todo", step_focus(1)),
+        Shortcut::new("k", "Previous todo", step_focus(-1)),
+        Shortcut::new("x", "Toggle focused todo", handle_toggle_focused),
+        Shortcut::new("e", "Edit focused todo", handle_edit_focused),
+        Shortcut::new("Delete", "Delete focused todo", handle_delete_focused),
+        Shortcut::new("Escape", "Cancel editing", handle_escape),
+    ];
+    let palette_shortcut = Shortcut::new("k", "Command palette", handle_toggle_palette.clone()).with_ctrl();
+
+    let palette_commands: Vec<Command> = if *palette_open {
+        let mut commands: Vec<Command> = shortcuts.iter().map(Command::from).collect();
+        commands.push(Command {
+            label: "Select all visible todos".into(),
+            hint: AttrValue::default(),
+            run: handle_select_all.clone(),
+        });
+        commands.push(Command {
+            label: "Clear filters".into(),
+            hint: AttrValue::default(),
+            run: handle_query_change.reform(|_| TodoQuery::default()),
+        });
+        for view in TodoView::ALL.into_iter().filter(|view| *view != TodoView::List) {
+            let navigator = navigator.clone();
+            commands.push(Command {
+                label: format!("Go to {} view", view.label()).into(),
+                hint: AttrValue::default(),
+                run: Callback::from(move |_| {
+                    if let Some(navigator) = &navigator {
+                        navigator.push(&view.route(list_id));
+                    }
+                }),
+            });
+        }
+        commands.extend(visible_todos.iter().map(|todo| {
+            let todo_id = todo.id;
+            Command {
+                label: todo.title.clone().into(),
+                hint: "Todo".into(),
+                run: Callback::from(move |_| focus_card(todo_id)),
+            }
+        }));
+        commands
+    } else {
+        Vec::new()
+    };
+
+    // only Ctrl+K, which closes it again, while the palette is open
+    use_shortcuts(if *palette_open {
+        vec![palette_shortcut]
+    } else {
+        let mut all = shortcuts;
+        all.push(palette_shortcut);
+        all
+    });
+
+    // creates the todos of an imported file in the list on screen
+    let handle_import = {
+        let todos = todos.clone();
+        let next_unsaved_id = next_unsaved_id.clone();
+        let queue = queue.clone();
+        let unreachable = unreachable.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        let fetch_todos = fetch_todos.clone();
+        let client = client.clone();
+        let report_error = report_error.clone();
+
+        Callback::from(move |imported: Vec<Todo>| {
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut created: Vec<(i64, Todo)> = Vec::new();
+            for todo in imported {
+                let todo = Todo { list_id, ..todo };
+                let unsaved_id = {
+                    let mut id = next_unsaved_id.borrow_mut();
+                    *id -= 1;
+                    *id
+                };
+                todos.dispatch(TodoListAction::Upsert(TodoResponse::unsaved(unsaved_id, &todo, &now)));
+                created.push((unsaved_id, todo));
+            }
+
+            let total = created.len();
+            if offline || queue.borrow().is_busy() {
+                for (local_id, todo) in created {
+                    queue.borrow_mut().push(PendingChange::Create { local_id, todo });
+                }
+                notice.set(Some(format!(
+                    "Imported {} todo(s). They will sync once the backend is reachable.",
+                    total
+                )));
+                return;
+            }
+
+            let todos = todos.clone();
+            let queue = queue.clone();
+            let unreachable = unreachable.clone();
+            let error = error.clone();
+            let notice = notice.clone();
+            let fetch_todos = fetch_todos.clone();
+            let client = client.clone();
+            let report_error = report_error.clone();
+            spawn_local(async move {
+                let new_todos: Vec<Todo> = created.iter().map(|(_, todo)| todo.clone()).collect();
+                let results = create_all(&client, &new_todos).await;
+
+                let mut failed: Vec<ApiError> = Vec::new();
+                let mut refetch = false;
+                for ((local_id, todo), result) in created.into_iter().zip(results) {
+                    match result {
+                        Ok(Some(saved)) => todos.dispatch(TodoListAction::Replace(local_id, saved)),
+                        Ok(None) => {
+                            // the backend did not echo the todo, so its id is unknown
+                            todos.dispatch(TodoListAction::Remove(local_id));
+                            refetch = true;
+                        }
+                        Err(ApiError::Network(_)) => {
+                            queue.borrow_mut().push(PendingChange::Create { local_id, todo });
+                            unreachable.set(true);
+                        }
+                        Err(err) => {
+                            todos.dispatch(TodoListAction::Remove(local_id));
+                            failed.push(err);
+                        }
+                    }
+                }
+                if refetch {
+                    fetch_todos.emit(());
+                }
+
+                if let Some(err) = failed.iter().find(|err| matches!(err, ApiError::Unauthorized(_))) {
+                    report_error.emit(("Could not import todos.", err.clone()));
+                } else if let Some(err) = failed.first() {
+                    error.set(Some(format!(
+                        "Imported {} of {} todos. {} failed: {}",
+                        total - failed.len(),
+                        total,
+                        failed.len(),
+                        err
+                    )));
+                } else {
+                    notice.set(Some(format!("Imported {} todo(s).", total)));
+                }
+            });
+        })
+    };
+
+    let handle_transfer_error = {
+        let error = error.clone();
+        Callback::from(move |message: String| error.set(Some(message)))
+    };
+
+    let handle_migrated = {
+        let fetch_todos = fetch_todos.clone();
+        let lists_version = lists_version.clone();
+        Callback::from(move |_| {
+            fetch_todos.emit(());
+            lists_version.set(*lists_version + 1);
+        })
+    };
+
+    let pending_changes = queue.borrow().len();
+
+    let handle_retry_sync = {
+        let fetch_todos = fetch_todos.clone();
+        Callback::from(move |_| fetch_todos.emit(()))
+    };
+
+    let handle_dismiss_error = {
+        let error = error.clone();
+        Callback::from(move |_| {
+            error.set(None);
+        })
+    };
+
+    let handle_dismiss_notice = {
+        let notice = notice.clone();
+        Callback::from(move |_| notice.set(None))
+    };
+
+    let quick_add = QuickAdd::parse(&new_draft.title, chrono::Local::now().naive_local());
+    let quick_add_list = quick_add
+        .list
+        .as_deref()
+        .and_then(|name| find_list(&lists, name))
+        .map(|list| AttrValue::from(list.name.clone()));
+
     html! {
     
         <div class="min-h-screen py-8 bg-gray-900">
-            <div class="max-w-4xl px-4 mx-auto">
-                <div class="mb-8 text-center">
-                    <h1 class="mb-2 text-4xl font-bold text-white">
-                        {"Todo Application"}
-                    </h1>
-                </div>
+            <div class="flex max-w-6xl gap-6 px-4 mx-auto">
+                <ListSidebar current={list_id} refresh={*lists_version} onerror={report_error.clone()} />
+                <div class="flex-1 min-w-0">
+                    <div class="mb-8 text-center">
+                        <h1 class="mb-2 text-4xl font-bold text-white">
+                            {"Todo Application"}
+                        </h1>
+                        <div class="flex items-center justify-center gap-4">
+                            if live {
+                                <span class="text-xs text-green-400" title="Changes from other sessions appear automatically">
+                                    {"● Live"}
+                                </span>
+                            }
+                            <ViewTabs current={TodoView::List} list_id={list_id} />
+                            <TransferMenu
+                                todos={visible_todos.clone()}
+                                partial={todos.has_more}
+                                list_id={list_id}
+                                query={query.clone()}
+                                onimport={handle_import}
+                                onerror={handle_transfer_error.clone()}
+                            />
+                            <MigrationImport
+                                offline={offline}
+                                oncomplete={handle_migrated}
+                                onerror={handle_transfer_error}
+                                onapierror={report_error.clone()}
+                            />
+                            <button
+                                onclick={handle_toggle_palette.reform(|_| ())}
+                                class="px-2 py-1 text-xs text-gray-400 border border-gray-600 rounded hover:text-white"
+                                title="Commands and keyboard shortcuts"
+                            >
+                                {"Ctrl+K"}
+                            </button>
+                        </div>
+                    </div>
+
+                    <div class="p-6 mb-6 bg-gray-800 rounded-lg shadow-lg">
+                        <form onsubmit={handle_add_todo} class="space-y-3">
+                            <div class="flex gap-4">
+                                <input
+                                    ref={new_title_ref.clone()}
+                                    type="text"
+                                    placeholder="What needs to be done? e.g. Call mom tomorrow 5pm !high #family @home"
+                                    value={new_draft.title.clone()}
+                                    oninput={handle_input_change}
+                                    disabled={*loading}
+                                    class="flex-1 px-4 py-3 text-white placeholder-gray-400 bg-gray-700 border border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                                />
+                                <button
+                                    type="button"
+                                    onclick={handle_toggle_details}
+                                    class="px-4 py-3 text-sm text-gray-300 bg-gray-700 rounded-lg hover:bg-gray-600"
+                                    title="More options"
+                                >
+                                    {if *show_details { "▴" } else { "▾" }}
+                                </button>
+                                <button
+                                    type="submit"
+                                    disabled={*loading || quick_add.title.is_empty()}
+                                    class="px-6 py-3 font-semibold text-white bg-blue-600 rounded-lg hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed"
+                                >
+                                    if *loading {
+                                        {"Adding..."}
+                                    } else {
+                                        {"Add Todo"}
+                                    }
+                                </button>
+                            </div>
+                            if quick_add.has_fields() {
+                                <QuickAddPreview
+                                    parsed={quick_add.clone()}
+                                    list_name={quick_add_list}
+                                />
+                            }
+                            if *show_details {
+                                <TodoFields
+                                    draft={(*new_draft).clone()}
+                                    onchange={handle_new_fields_change}
+                                    disabled={*loading}
+                                />
+                            }
+                        </form>
+                    </div>
 
-                <div class="p-6 mb-6 bg-gray-800 rounded-lg shadow-lg">
-                    <form onsubmit={handle_add_todo} class="flex gap-4">
-                        <input
-                            type="text"
-                            placeholder="What needs to be done?"
-                            value={(*input_value).clone()}
-                            oninput={handle_input_change}
-                            disabled={*loading}
-                            class="flex-1 px-4 py-3 text-white placeholder-gray-400 bg-gray-700 border border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                    // the undo toast always shows so its timer, which commits held deletes, runs;
+                    // an error is stacked above it rather than hidden
+                    if let Some(toast) = &*undo {
+                        <Toast
+                            key={toast.seq}
+                            message={toast.message.clone()}
+                            ondismiss={handle_dismiss_undo}
+                            kind={ToastKind::Info}
+                            action={AttrValue::from("Undo")}
+                            onaction={handle_undo}
                         />
-                        <button
-                            type="submit"
-                            disabled={*loading || input_value.trim().is_empty()}
-                            class="px-6 py-3 font-semibold text-white bg-blue-600 rounded-lg hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed"
-                        >
-                            if *loading {
-                                {"Adding..."}
-                            } else {
-                                {"Add Todo"}
+                    }
+                    if let Some(err) = &*error {
+                        <Toast message={err.clone()} ondismiss={handle_dismiss_error} stacked={undo.is_some()} />
+                    } else if let (None, Some(message)) = (&*undo, &*notice) {
+                        <Toast message={message.clone()} ondismiss={handle_dismiss_notice} kind={ToastKind::Info} />
+                    }
+
+                    if offline || pending_changes > 0 {
+                        <div class="flex items-center justify-between gap-4 px-4 py-3 mb-6 text-sm text-yellow-200 border border-yellow-600 rounded-lg bg-yellow-900/40">
+                            <span>
+                                if offline {
+                                    {"You're offline. Showing todos saved on this device; changes sync when the connection is back."}
+                                } else {
+                                    {"Syncing changes made offline..."}
+                                }
+                                if pending_changes > 0 {
+                                    {format!(" {} change(s) waiting.", pending_changes)}
+                                }
+                            </span>
+                            if online && !*loading {
+                                <button
+                                    onclick={handle_retry_sync}
+                                    class="px-3 py-1 text-yellow-100 rounded hover:bg-yellow-800"
+                                >
+                                    {"Retry"}
+                                </button>
                             }
-                        </button>
-                    </form>
-                </div>
+                        </div>
+                    }
 
-                if todos.is_empty() && !*loading {
-                    <div class="py-12 text-center">
-                        <div class="mb-4 text-4xl">{"🎯"}</div>
-                        <h3 class="mb-2 text-xl font-semibold text-white">{"No todos yet!"}</h3>
-                        <p class="text-gray-400">{"Add your first todo above to get started"}</p>
-                    </div>
-                } else {
-                    <div class="space-y-3">
-                        { for todos.iter().map(|todo| {
-                            let is_editing = *edit_todo_id == Some(todo.id);
-                            let is_completed = todo.completed;
-
-                            let delete_click = {
-                                let handle_delete = handle_delete_todo.clone();
-                                let todo_id = todo.id;
-                                Callback::from(move |_| handle_delete.emit(todo_id))
-                            };
-
-                            let toggle_click = {
-                                let handle_toggle = handle_toggle_status.clone();
-                                let todo_id = todo.id;
-                                Callback::from(move |_| handle_toggle.emit(todo_id))
-                            };
-
-                            let edit_click = {
-                                let handle_edit = handle_edit_click.clone();
-                                let todo_id = todo.id;
-                                let title = todo.title.clone();
-                                Callback::from(move |_| handle_edit.emit((todo_id, title.clone())))
-                            };
-
-                            html! {
-                                <div class={format!("bg-gray-800 rounded-lg p-4 shadow-md transition-all duration-200 hover:bg-gray-750 {}", if is_completed { "opacity-75" } else { "" })}>
-                                    <div class="flex items-center gap-3">
-                                        <button
-                                            onclick={toggle_click}
-                                            disabled={*loading}
-                                            class={format!("w-5 h-5 rounded-full border-2 flex items-center justify-center text-xs {}",
+                    if !todos.items.is_empty() {
+                        <TodoToolbar
+                            query={query.clone()}
+                            onchange={handle_query_change.clone()}
+                            tags={collect_tags(&todos.items)}
+                            visible={visible_todos.len()}
+                            loaded={todos.items.len()}
+                            total={todos.total.unwrap_or(todos.items.len())}
+                            onloadall={handle_load_all}
+                            loading_all={*loading_more}
+                            search_ref={search_ref.clone()}
+                        />
+                    }
+
+                    if *palette_open {
+                        <CommandPalette commands={palette_commands} onclose={handle_close_palette} />
+                    }
+
+                    if *confirm_bulk_delete {
+                        <ConfirmDialog
+                            title="Delete todos?"
+                            message={format!("{} selected todo(s) will be deleted.", selected_count)}
+                            confirm_label="Delete"
+                            onconfirm={handle_confirm_bulk_delete}
+                            oncancel={handle_cancel_bulk_delete}
+                        />
+                    }
+
+                    if selected_count > 0 {
+                        <BulkBar
+                            selected={selected_count}
+                            visible={visible_ids.len()}
+                            onselectall={handle_select_all}
+                            onclear={handle_clear_selection}
+                            onaction={handle_bulk_action}
+                            current_list={list_id}
+                        />
+                    }
+
+                    if todos.items.is_empty() && !*loading {
+                        <div class="py-12 text-center">
+                            <div class="mb-4 text-4xl">{"🎯"}</div>
+                            <h3 class="mb-2 text-xl font-semibold text-white">{"No todos yet!"}</h3>
+                            <p class="text-gray-400">{"Add your first todo above to get started"}</p>
+                        </div>
+                    } else if visible_todos.is_empty() && query.is_filtered() {
+                        <div class="py-12 text-center">
+                            <div class="mb-4 text-4xl">{"🔍"}</div>
+                            <h3 class="mb-2 text-xl font-semibold text-white">{"No matching todos"}</h3>
+                            <button
+                                onclick={handle_clear_filters}
+                                class="text-blue-400 hover:underline"
+                            >
+                                {"Clear filters"}
+                            </button>
+                        </div>
+                    } else {
+                        <div class="space-y-3">
+                            { for visible_todos.iter().enumerate().map(|(index, todo)| {
+                                let is_editing = *edit_todo_id == Some(todo.id);
+                                let is_completed = todo.completed;
+                                let pending_sync = queue.borrow().touches(todo.id);
+                                let is_unsaved = queue.borrow().is_unsaved(todo.id);
+
+                                let delete_click = {
+                                    let handle_delete = handle_delete_todo.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |_| handle_delete.emit(todo_id))
+                                };
+
+                                let toggle_click = {
+                                    let handle_toggle = handle_toggle_status.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |_| handle_toggle.emit(todo_id))
+                                };
+
+                                let edit_click = {
+                                    let handle_edit = handle_edit_click.clone();
+                                    let todo = todo.clone();
+                                    Callback::from(move |_| handle_edit.emit(todo.clone()))
+                                };
+                                let checklist_click = {
+                                    let handle_toggle_checklist = handle_toggle_checklist.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |_| handle_toggle_checklist.emit(todo_id))
+                                };
+                                let checklist_change = {
+                                    let handle_checklist_change = handle_checklist_change.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |items| handle_checklist_change.emit((todo_id, items)))
+                                };
+                                let show_checklist = expanded_checklists.contains(&todo.id);
+                                let draggable = can_reorder && !is_unsaved && !is_editing;
+                                let last_index = visible_todos.len() - 1;
+
+                                let drag_start = {
+                                    let dragging = dragging.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |e: DragEvent| {
+                                        // Firefox only starts a drag when some data is set
+                                        if let Some(data) = e.data_transfer() {
+                                            let _ = data.set_data("text/plain", &todo_id.to_string());
+                                        }
+                                        dragging.set(Some(index));
+                                    })
+                                };
+                                let drag_over = {
+                                    let drop_target = drop_target.clone();
+                                    let is_dragging = dragging.is_some();
+                                    Callback::from(move |e: DragEvent| {
+                                        if is_dragging {
+                                            e.prevent_default();
+                                            if *drop_target != Some(index) {
+                                                drop_target.set(Some(index));
+                                            }
+                                        }
+                                    })
+                                };
+                                let drop = {
+                                    let handle_move = handle_move.clone();
+                                    let handle_drag_end = handle_drag_end.clone();
+                                    let from = *dragging;
+                                    Callback::from(move |e: DragEvent| {
+                                        e.prevent_default();
+                                        if let Some(from) = from {
+                                            handle_move.emit((from, index));
+                                        }
+                                        handle_drag_end.emit(e);
+                                    })
+                                };
+                                // Alt+↑ / Alt+↓ moves the focused card
+                                let key_move = {
+                                    let handle_move = handle_move.clone();
+                                    Callback::from(move |e: KeyboardEvent| {
+                                        if !draggable || !e.alt_key() {
+                                            return;
+                                        }
+                                        let to = match e.key().as_str() {
+                                            "ArrowUp" if index > 0 => index - 1,
+                                            "ArrowDown" if index < last_index => index + 1,
+                                            _ => return,
+                                        };
+                                        e.prevent_default();
+                                        handle_move.emit((index, to));
+                                    })
+                                };
+                                let drag_class = if *dragging == Some(index) {
+                                    "opacity-50"
+                                } else if *drop_target == Some(index) && dragging.is_some() {
+                                    "ring-2 ring-blue-500"
+                                } else {
+                                    ""
+                                };
+                                let is_overdue = !is_completed
+                                    && todo.due().is_some_and(|due| due < chrono::Local::now().date_naive());
+                                let is_selected = selected.contains(&todo.id);
+                                let select_click = {
+                                    let handle_select = handle_select.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |e: MouseEvent| handle_select.emit((todo_id, e.shift_key())))
+                                };
+
+                                let card_focus = {
+                                    let focused = focused.clone();
+                                    let todo_id = todo.id;
+                                    Callback::from(move |_: FocusEvent| focused.set(Some(todo_id)))
+                                };
+
+                                html! {
+                                    <div
+                                        key={todo.id}
+                                        data-todo-id={todo.id.to_string()}
+                                        tabindex="0"
+                                        onfocus={card_focus}
+                                        draggable={draggable.to_string()}
+                                        ondragstart={drag_start}
+                                        ondragover={drag_over}
+                                        ondrop={drop}
+                                        ondragend={handle_drag_end.clone()}
+                                        onkeydown={key_move}
+                                        class={format!("bg-gray-800 rounded-lg p-4 shadow-md transition-all duration-200 hover:bg-gray-750 focus:outline-none focus:ring-1 focus:ring-gray-500 {} {}",
+                                            if is_completed || is_unsaved { "opacity-75" } else { "" },
+                                            if is_selected && drag_class.is_empty() { "ring-1 ring-blue-500" } else { drag_class },
+                                        )}
+                                    >
+                                        <div class="flex items-center gap-3">
+                                            <input
+                                                type="checkbox"
+                                                checked={is_selected}
+                                                onclick={select_click}
+                                                class="accent-blue-500"
+                                                title="Select (shift-click for a range)"
+                                            />
+                                            if draggable {
+                                                <span class="text-gray-500 cursor-grab select-none" title="Drag to reorder (or Alt+↑/↓)">
+                                                    {"⋮⋮"}
+                                                </span>
+                                            }
+                                            <button
+                                                onclick={toggle_click}
+                                                disabled={is_unsaved}
+                                                class={format!("w-5 h-5 rounded-full border-2 flex items-center justify-center text-xs {}",
+                                                    if is_completed {
+                                                        "bg-green-500 border-green-500 text-white"
+                                                    } else {
+                                                        "border-gray-400 hover:border-green-400"
+                                                    }
+                                                )}
+                                            >
                                                 if is_completed {
-                                                    "bg-green-500 border-green-500 text-white"
+                                                    {"✓"}
+                                                }
+                                            </button>
+
+                                            <div class="flex-1">
+                                                if is_editing {
+                                                    <div class="space-y-3">
+                                                        <div class="flex gap-2">
+                                                            <input
+                                                                type="text"
+                                                                value={edit_draft.title.clone()}
+                                                                oninput={handle_edit_input_change.clone()}
+                                                                class="flex-1 px-3 py-2 text-white bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500"
+                                                            />
+                                                            <button
+                                                                onclick={handle_save_edit.clone()}
+                                                                class="px-3 py-2 text-sm text-white bg-green-600 rounded hover:bg-green-700"
+                                                            >
+                                                                {"Save"}
+                                                            </button>
+                                                            <button
+                                                                onclick={handle_cancel_edit.clone()}
+                                                                class="px-3 py-2 text-sm text-white bg-gray-600 rounded hover:bg-gray-700"
+                                                            >
+                                                                {"Cancel"}
+                                                            </button>
+                                                        </div>
+                                                        <TodoFields
+                                                            draft={(*edit_draft).clone()}
+                                                            onchange={handle_edit_fields_change.clone()}
+                                                            disabled={*loading}
+                                                        />
+                                                    </div>
                                                 } else {
-                                                    "border-gray-400 hover:border-green-400"
+                                                    <div class="flex items-center justify-between gap-3">
+                                                        <div class="flex flex-wrap items-center gap-2">
+                                                            <span class={format!("text-white {}", if is_completed { "line-through opacity-75" } else { "" })}>
+                                                                {&todo.title}
+                                                            </span>
+                                                            if let Some((done, total)) = checklist_progress(&todo.checklist) {
+                                                                <button
+                                                                    onclick={checklist_click.clone()}
+                                                                    class={format!("px-2 py-0.5 rounded text-xs {}",
+                                                                        if done == total { "bg-green-900 text-green-300" } else { "bg-gray-700 text-gray-300" }
+                                                                    )}
+                                                                    title="Show checklist"
+                                                                >
+                                                                    {format!("☑ {}/{}", done, total)}
+                                                                </button>
+                                                            }
+                                                            if let Some(description) = todo.description.as_ref() {
+                                                                if !description.trim().is_empty() {
+                                                                    <span class="text-sm text-gray-400">
+                                                                        {description.clone()}
+                                                                    </span>
+                                                                }
+                                                            }
+                                                            <span
+                                                                class="text-xs text-gray-500"
+                                                                title={format!("Created {} · Updated {}", format_timestamp(&todo.created_at), format_timestamp(&todo.updated_at))}
+                                                            >
+                                                                {format!("Updated {}", format_relative(&todo.updated_at))}
+                                                            </span>
+                                                        </div>
+                                                        <div class="flex flex-wrap items-center justify-end gap-2">
+                                                            { for todo.tags.iter().map(|tag| html! {
+                                                                <span class="px-2 py-1 text-xs text-purple-300 rounded bg-purple-900">
+                                                                    {format!("#{}", tag)}
+                                                                </span>
+                                                            }) }
+                                                            if let Some(due) = todo.due() {
+                                                                <span class={format!("px-2 py-1 rounded text-xs {}",
+                                                                    if is_overdue { "bg-red-900 text-red-300" } else { "bg-gray-700 text-gray-300" }
+                                                                )}>
+                                                                    {format!("Due {}", due.format("%b %-d, %Y"))}
+                                                                </span>
+                                                            }
+                                                            if let Some(recurrence) = todo.repeats() {
+                                                                <span class="px-2 py-1 text-xs text-teal-300 rounded bg-teal-900" title="Completing it schedules the next occurrence">
+                                                                    {format!("↻ {}", recurrence.describe())}
+                                                                </span>
+                                                            }
+                                                            if pending_sync {
+                                                                <span class="px-2 py-1 text-xs text-gray-300 bg-gray-700 rounded" title="Saved on this device, not synced yet">
+                                                                    {"⟳ Not synced"}
+                                                                </span>
+                                                            }
+                                                            <span class={format!("px-2 py-1 rounded text-xs {}", priority_badge_class(todo.priority))}>
+                                                                {todo.priority.label()}
+                                                            </span>
+                                                            <span class={format!("px-2 py-1 rounded text-xs {}", status_badge_class(todo.status()))}>
+                                                                {todo.status().label()}
+                                                            </span>
+                                                        </div>
+                                                    </div>
                                                 }
-                                            )}
-                                        >
-                                            if is_completed {
-                                                {"✓"}
-                                            }
-                                        </button>
+                                            </div>
 
-                                        <div class="flex-1">
-                                            if is_editing {
-                                                <div class="flex gap-2">
-                                                    <input
-                                                        type="text"
-                                                        value={(*edit_todo_text).clone()}
-                                                        oninput={handle_edit_input_change.clone()}
-                                                        class="flex-1 px-3 py-2 text-white bg-gray-700 border border-gray-600 rounded focus:outline-none focus:ring-2 focus:ring-blue-500"
-                                                    />
+                                            if !is_editing {
+                                                <div class="flex gap-1">
                                                     <button
-                                                        onclick={handle_save_edit.clone()}
-                                                        class="px-3 py-2 text-sm text-white bg-green-600 rounded hover:bg-green-700"
+                                                        onclick={checklist_click}
+                                                        class={format!("p-2 rounded hover:bg-gray-700 {}", if show_checklist { "text-white" } else { "text-gray-400" })}
+                                                        title="Checklist"
                                                     >
-                                                        {"Save"}
+                                                        {"☑"}
                                                     </button>
                                                     <button
-                                                        onclick={handle_cancel_edit.clone()}
-                                                        class="px-3 py-2 text-sm text-white bg-gray-600 rounded hover:bg-gray-700"
+                                                        onclick={edit_click}
+                                                        disabled={is_unsaved}
+                                                        class="p-2 text-blue-400 rounded hover:text-blue-300 hover:bg-blue-900 disabled:opacity-50"
+                                                        title="Edit todo"
                                                     >
-                                                        {"Cancel"}
+                                                        {"✏️"}
+                                                    </button>
+                                                    <button
+                                                        onclick={delete_click}
+                                                        disabled={is_unsaved}
+                                                        class="p-2 text-red-400 rounded hover:text-red-300 hover:bg-red-900 disabled:opacity-50"
+                                                        title="Delete todo"
+                                                    >
+                                                        {"🗑️"}
                                                     </button>
-                                                </div>
-                                            } else {
-                                                <div class="flex items-center justify-between gap-3">
-                                                    <div class="flex flex-wrap items-center gap-2">
-                                                        <span class={format!("text-white {}", if is_completed { "line-through opacity-75" } else { "" })}>
-                                                            {&todo.title}
-                                                        </span>
-                                                        if let Some(description) = todo.description.as_ref() {
-                                                            if !description.trim().is_empty() {
-                                                                <span class="text-sm text-gray-400">
-                                                                    {description.clone()}
-                                                                </span>
-                                                            }
-                                                        }
-                                                    </div>
-                                                    <div class="flex items-center gap-2">
-                                                        <span class={format!("px-2 py-1 rounded text-xs {}",
-                                                            if is_completed {
-                                                                "bg-green-900 text-green-300"
-                                                            } else {
-                                                                "bg-yellow-900 text-yellow-300"
-                                                            }
-                                                        )}>
-                                                            {if is_completed { "Completed" } else { "Pending" }}
-                                                        </span>
-                                                    </div>
                                                 </div>
                                             }
                                         </div>
-
-                                        if !is_editing {
-                                            <div class="flex gap-1">
-                                                <button
-                                                    onclick={edit_click}
-                                                    disabled={*loading}
-                                                    class="p-2 text-blue-400 rounded hover:text-blue-300 hover:bg-blue-900 disabled:opacity-50"
-                                                    title="Edit todo"
-                                                >
-                                                    {"✏️"}
-                                                </button>
-                                                <button
-                                                    onclick={delete_click}
-                                                    disabled={*loading}
-                                                    class="p-2 text-red-400 rounded hover:text-red-300 hover:bg-red-900 disabled:opacity-50"
-                                                    title="Delete todo"
-                                                >
-                                                    {"🗑️"}
-                                                </button>
+                                        if show_checklist && !is_editing {
+                                            <div class="pl-8">
+                                                <Checklist
+                                                    items={todo.checklist.clone()}
+                                                    onchange={checklist_change}
+                                                    disabled={is_unsaved}
+                                                />
+                                                <label class="flex items-center gap-2 mt-2 text-xs text-gray-400">
+                                                    <input
+                                                        type="checkbox"
+                                                        checked={*checklist_auto_complete}
+                                                        onclick={handle_auto_complete_change.clone()}
+                                                    />
+                                                    {"Complete the todo when every item is done"}
+                                                </label>
                                             </div>
                                         }
                                     </div>
-                                </div>
-                            }
-                        }) }
-                    </div>
-                }
+                                }
+                            }) }
+                        </div>
+                        if todos.has_more && !offline {
+                            <LoadMore onload={handle_load_more} disabled={*loading_more} />
+                        }
+                    }
 
-                if !todos.is_empty() {
-                    <div class="p-6 mt-6 bg-gray-800 rounded-lg shadow-md">
-                        <div class="grid grid-cols-3 gap-4 text-center">
-                            <div class="p-4 bg-gray-700 rounded-lg">
-                                <div class="text-2xl font-bold text-blue-400">{todos.len()}</div>
-                                <div class="text-sm text-gray-400">{"Total"}</div>
-                            </div>
-                            <div class="p-4 bg-gray-700 rounded-lg">
-                                <div class="text-2xl font-bold text-green-400">{todos.iter().filter(|t| t.completed).count()}</div>
-                                <div class="text-sm text-gray-400">{"Completed"}</div>
-                            </div>
-                            <div class="p-4 bg-gray-700 rounded-lg">
-                                <div class="text-2xl font-bold text-yellow-400">{todos.iter().filter(|t| !t.completed).count()}</div>
-                                <div class="text-sm text-gray-400">{"Pending"}</div>
+                    if !todos.items.is_empty() {
+                        // completed and pending only cover loaded pages, the total is the server's
+                        <div class="p-6 mt-6 bg-gray-800 rounded-lg shadow-md">
+                            <div class="grid grid-cols-3 gap-4 text-center">
+                                <div class="p-4 bg-gray-700 rounded-lg">
+                                    <div class="text-2xl font-bold text-blue-400">{todos.total.unwrap_or(todos.items.len())}</div>
+                                    <div class="text-sm text-gray-400">{"Total"}</div>
+                                </div>
+                                <div class="p-4 bg-gray-700 rounded-lg">
+                                    <div class="text-2xl font-bold text-green-400">{todos.items.iter().filter(|t| t.completed).count()}</div>
+                                    <div class="text-sm text-gray-400">{if todos.has_more { "Completed (loaded)" } else { "Completed" }}</div>
+                                </div>
+                                <div class="p-4 bg-gray-700 rounded-lg">
+                                    <div class="text-2xl font-bold text-yellow-400">{todos.items.iter().filter(|t| !t.completed).count()}</div>
+                                    <div class="text-sm text-gray-400">{if todos.has_more { "Pending (loaded)" } else { "Pending" }}</div>
+                                </div>
                             </div>
                         </div>
-                    </div>
-                }
+                    }
+                </div>
             </div>
         </div>
     }
@@ -6,9 +6,13 @@ pub struct Todo {
     pub task: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+/// Workflow state of a todo, shown as the columns of the board.
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
 pub enum Status {
+    #[default]
     PENDING,
+    IN_PROGRESS,
     DONE,
 }
 
@@ -27,16 +31,28 @@ pub struct UpdateTodoType {
 }
 
 impl Status {
+    pub const ALL: [Status; 3] = [Status::PENDING, Status::IN_PROGRESS, Status::DONE];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Status::PENDING => "PENDING",
+            Status::IN_PROGRESS => "IN_PROGRESS",
             Status::DONE => "DONE",
         }
     }
 
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::PENDING => "Pending",
+            Status::IN_PROGRESS => "In progress",
+            Status::DONE => "Done",
+        }
+    }
+
     pub fn from_str(status: &str) -> Self {
         match status {
             "PENDING" => Status::PENDING,
+            "IN_PROGRESS" => Status::IN_PROGRESS,
             "DONE" => Status::DONE,
             _ => Status::PENDING,
         }
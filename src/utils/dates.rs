@@ -0,0 +1,132 @@
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, Utc};
+
+use crate::api::models::TodoResponse;
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Parses a backend timestamp; values without an offset are taken as UTC.
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Local))
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+                .map(|date| date.and_utc().with_timezone(&Local))
+        })
+}
+
+/// `created_at`/`updated_at` in the browser's timezone, e.g. `Mar 4, 2024 14:05`.
+///
+/// Unparseable values are shown as received.
+pub fn format_timestamp(value: &str) -> String {
+    match parse_timestamp(value) {
+        Some(date) => date.format("%b %-d, %Y %H:%M").to_string(),
+        None => value.to_string(),
+    }
+}
+
+/// Relative form for recent timestamps ("just now", "5 min ago", "3 h ago"),
+/// falling back to [`format_timestamp`].
+pub fn format_relative(value: &str) -> String {
+    let Some(date) = parse_timestamp(value) else {
+        return value.to_string();
+    };
+    let minutes = (Utc::now() - date.with_timezone(&Utc)).num_minutes();
+    match minutes {
+        m if m < 1 => "just now".to_string(),
+        m if m < 60 => format!("{} min ago", m),
+        m if m < 24 * 60 => format!("{} h ago", m / 60),
+        _ => format_timestamp(value),
+    }
+}
+
+/// Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+/// The seven days of the week containing `date`.
+pub fn week_days(date: NaiveDate) -> Vec<NaiveDate> {
+    week_start(date).iter_days().take(7).collect()
+}
+
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// `date` moved by `months`, clamped to the last day of shorter months.
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let shifted = if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.unwrap_or(date)
+}
+
+/// Whole weeks (Monday first) covering the month of `date`, for a month grid.
+pub fn month_grid(date: NaiveDate) -> Vec<NaiveDate> {
+    let first = month_start(date);
+    let last = add_months(first, 1) - Days::new(1);
+    let end = week_start(last) + Days::new(6);
+    week_start(first)
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AgendaGroup {
+    Overdue,
+    Today,
+    Upcoming,
+}
+
+impl AgendaGroup {
+    pub const ALL: [AgendaGroup; 3] = [
+        AgendaGroup::Overdue,
+        AgendaGroup::Today,
+        AgendaGroup::Upcoming,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgendaGroup::Overdue => "Overdue",
+            AgendaGroup::Today => "Today",
+            AgendaGroup::Upcoming => "Upcoming",
+        }
+    }
+
+    /// Group of an open todo due on `due`; completed todos are never overdue.
+    pub fn of(todo: &TodoResponse, due: NaiveDate, today: NaiveDate) -> Option<AgendaGroup> {
+        if due < today {
+            (!todo.completed).then_some(AgendaGroup::Overdue)
+        } else if due == today {
+            Some(AgendaGroup::Today)
+        } else {
+            Some(AgendaGroup::Upcoming)
+        }
+    }
+}
+
+/// Todos with a due date grouped by day, earliest first.
+pub fn group_by_due(todos: &[TodoResponse]) -> Vec<(NaiveDate, Vec<TodoResponse>)> {
+    let mut dated: Vec<(NaiveDate, TodoResponse)> = todos
+        .iter()
+        .filter_map(|todo| todo.due().map(|due| (due, todo.clone())))
+        .collect();
+    dated.sort_by(|(a, x), (b, y)| a.cmp(b).then(x.priority.cmp(&y.priority).reverse()));
+
+    let mut groups: Vec<(NaiveDate, Vec<TodoResponse>)> = Vec::new();
+    for (due, todo) in dated {
+        match groups.last_mut() {
+            Some((day, todos)) if *day == due => todos.push(todo),
+            _ => groups.push((due, vec![todo])),
+        }
+    }
+    groups
+}
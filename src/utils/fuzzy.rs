@@ -0,0 +1,59 @@
+/// Scores `text` against `pattern` when every pattern character appears in
+/// order (case-insensitive); `None` when it does not match.
+///
+/// Higher is better: runs of consecutive characters, matches at the start of a
+/// word and an early first match all count, while skipped characters cost a
+/// little. An empty pattern matches everything with a score of 0.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i32> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if pattern.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for (index, c) in text.iter().enumerate() {
+        if next == pattern.len() {
+            break;
+        }
+        if *c != pattern[next] {
+            continue;
+        }
+        let word_start = index == 0 || !text[index - 1].is_alphanumeric();
+        score += 1;
+        if word_start {
+            score += 8;
+        }
+        match previous {
+            Some(prev) if prev + 1 == index => score += 5,
+            Some(prev) => score -= (index - prev - 1).min(5) as i32,
+            // the earlier the first match, the better
+            None => score -= index.min(10) as i32,
+        }
+        previous = Some(index);
+        next += 1;
+    }
+
+    (next == pattern.len()).then_some(score)
+}
+
+/// Items of `items` matching `pattern`, best first; ties keep their order.
+pub fn fuzzy_filter<'a, T>(
+    pattern: &str,
+    items: &'a [T],
+    text: impl Fn(&T) -> &str,
+) -> Vec<&'a T> {
+    let mut scored: Vec<(i32, usize, &T)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| fuzzy_score(pattern, text(item)).map(|score| (score, index, item)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, item)| item).collect()
+}
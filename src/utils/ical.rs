@@ -0,0 +1,499 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use crate::api::models::{parse_due_date, Priority, Todo, TodoResponse};
+use crate::types::Status;
+use crate::utils::dates::parse_timestamp;
+use crate::utils::recurrence::Recurrence;
+use crate::utils::transfer::ImportError;
+
+const PRODID: &str = "-//yew-boilerplate//Todos//EN";
+/// Longest content line in octets before it is folded (RFC 5545, 3.1).
+const LINE_LIMIT: usize = 75;
+/// Stands for `X-FROM=COMPLETION`, which calendar tools would not accept inside RRULE.
+const REPEAT_FROM: &str = "X-REPEAT-FROM";
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// `line` split into CRLF-terminated lines of at most [`LINE_LIMIT`] octets,
+/// never inside a character.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            // the leading space counts towards the next line
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+fn utc_stamp(date: DateTime<Utc>) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// A backend timestamp as an iCalendar UTC date-time.
+fn timestamp(value: &str) -> Option<String> {
+    parse_timestamp(value).map(|date| utc_stamp(date.with_timezone(&Utc)))
+}
+
+/// `DUE` with its parameters: a plain date stays a date, a date-time becomes UTC.
+fn due_property(due: &str) -> Option<String> {
+    if due.len() > 10 {
+        if let Ok(date) = DateTime::parse_from_rfc3339(due) {
+            return Some(format!("DUE:{}", utc_stamp(date.with_timezone(&Utc))));
+        }
+    }
+    parse_due_date(due).map(|date| format!("DUE;VALUE=DATE:{}", date.format("%Y%m%d")))
+}
+
+fn ical_priority(priority: Priority) -> u8 {
+    match priority {
+        Priority::Urgent => 1,
+        Priority::High => 3,
+        Priority::Medium => 5,
+        Priority::Low => 9,
+    }
+}
+
+/// `PRIORITY` 1 (highest) to 9 (lowest); 0 means undefined.
+fn priority_from_ical(value: &str) -> Priority {
+    match value.trim().parse::<u8>() {
+        Ok(1) => Priority::Urgent,
+        Ok(2..=4) => Priority::High,
+        Ok(6..=9) => Priority::Low,
+        _ => Priority::Medium,
+    }
+}
+
+fn ical_status(status: Status) -> &'static str {
+    match status {
+        Status::PENDING => "NEEDS-ACTION",
+        Status::IN_PROGRESS => "IN-PROCESS",
+        Status::DONE => "COMPLETED",
+    }
+}
+
+/// `todos` as an iCalendar file with one VTODO each.
+///
+/// Checklists have no VTODO counterpart and are left out.
+pub fn export_ics(todos: &[TodoResponse]) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Todos".to_string(),
+    ];
+    let now = utc_stamp(Utc::now());
+    for todo in todos {
+        let status = todo.status();
+        let modified = timestamp(&todo.updated_at);
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:todo-{}@yew-boilerplate", todo.id));
+        lines.push(format!("DTSTAMP:{}", modified.clone().unwrap_or_else(|| now.clone())));
+        if let Some(created) = timestamp(&todo.created_at) {
+            lines.push(format!("CREATED:{}", created));
+        }
+        if let Some(modified) = &modified {
+            lines.push(format!("LAST-MODIFIED:{}", modified));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&todo.title)));
+        if let Some(description) = todo.description.as_deref().filter(|text| !text.is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("STATUS:{}", ical_status(status)));
+        if status == Status::DONE {
+            lines.push("PERCENT-COMPLETE:100".to_string());
+            if let Some(modified) = &modified {
+                lines.push(format!("COMPLETED:{}", modified));
+            }
+        }
+        lines.push(format!("PRIORITY:{}", ical_priority(todo.priority)));
+        if let Some(due) = todo.due_date.as_deref().and_then(due_property) {
+            lines.push(due);
+        }
+        if !todo.tags.is_empty() {
+            let tags: Vec<String> = todo.tags.iter().map(|tag| escape_text(tag)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        // no DTSTART: todos have no start, and one equal to DUE is invalid (DUE must be later)
+        if let Some(recurrence) = todo.repeats() {
+            let from_completion = recurrence.from_completion;
+            let rule = Recurrence {
+                from_completion: false,
+                ..recurrence
+            };
+            lines.push(format!("RRULE:{}", rule.to_rule()));
+            if from_completion {
+                lines.push(format!("{}:COMPLETION", REPEAT_FROM));
+            }
+        }
+        lines.push("END:VTODO".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// A content line: name, parameters and value.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Splits `NAME;PARAM=value;PARAM="a:b":VALUE`; quoted parameter values may hold `:` and `;`.
+fn parse_property(line: &str) -> Option<Property> {
+    let mut quoted = false;
+    let mut split = None;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => {
+                split = Some(index);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let (head, value) = line.split_at(split?);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+    Some(Property {
+        name,
+        params,
+        value: value[1..].to_string(),
+    })
+}
+
+/// `DUE` (or `DTSTART`) in the form todos store: `YYYY-MM-DD` for dates,
+/// RFC 3339 in the local timezone for date-times. Floating and `TZID` times
+/// are read as local time.
+fn due_from_ical(property: &Property) -> Option<String> {
+    let value = property.value.trim();
+    if property.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(|date| date.format("%Y-%m-%d").to_string());
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let date = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(date.and_utc().with_timezone(&Local).to_rfc3339());
+    }
+    let date = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Local
+        .from_local_datetime(&date)
+        .earliest()
+        .map(|date| date.to_rfc3339())
+}
+
+fn todo_from_properties(properties: &[Property]) -> Todo {
+    let mut todo = Todo::default();
+    let mut repeat_from_completion = false;
+    for property in properties {
+        match property.name.as_str() {
+            "SUMMARY" => todo.title = unescape_text(&property.value),
+            "DESCRIPTION" => {
+                let description = unescape_text(&property.value);
+                todo.description = (!description.trim().is_empty()).then_some(description);
+            }
+            "STATUS" => {
+                todo.status = match property.value.trim().to_ascii_uppercase().as_str() {
+                    "NEEDS-ACTION" => Some(Status::PENDING),
+                    "IN-PROCESS" => Some(Status::IN_PROGRESS),
+                    // cancelled todos are finished as far as this app can tell
+                    "COMPLETED" | "CANCELLED" => Some(Status::DONE),
+                    _ => None,
+                }
+            }
+            "COMPLETED" => todo.completed = true,
+            "PERCENT-COMPLETE" => todo.completed |= property.value.trim() == "100",
+            "PRIORITY" => todo.priority = priority_from_ical(&property.value),
+            "DUE" => todo.due_date = due_from_ical(property),
+            // a start date is the nearest thing to a due date when there is none
+            "DTSTART" if todo.due_date.is_none() => todo.due_date = due_from_ical(property),
+            "CATEGORIES" => {
+                for tag in split_list(&property.value) {
+                    if !tag.is_empty() && !todo.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                        todo.tags.push(tag);
+                    }
+                }
+            }
+            "RRULE" => todo.recurrence = Recurrence::parse(&property.value).map(|rule| rule.to_rule()),
+            REPEAT_FROM => repeat_from_completion = property.value.trim().eq_ignore_ascii_case("COMPLETION"),
+            _ => {}
+        }
+    }
+    if repeat_from_completion {
+        todo.recurrence = todo
+            .recurrence
+            .as_deref()
+            .and_then(Recurrence::parse)
+            .map(|rule| Recurrence {
+                from_completion: true,
+                ..rule
+            }
+            .to_rule());
+    }
+    todo
+}
+
+/// Comma-separated text values, honouring `\,` escapes.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push('\\');
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(unescape_text(std::mem::take(&mut current).trim())),
+            c => current.push(c),
+        }
+    }
+    items.push(unescape_text(current.trim()));
+    items
+}
+
+/// The VTODO entries of an iCalendar file as new todos; events and other
+/// components are skipped.
+pub fn import_ics(text: &str) -> Result<Vec<Todo>, ImportError> {
+    // unfold: a line starting with a space or tab continues the previous one
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut todos = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    // components nested in a VTODO, such as VALARM, whose lines are not the todo's
+    let mut nested = 0;
+    for property in lines.iter().filter_map(|line| parse_property(line)) {
+        let value = property.value.trim().to_ascii_uppercase();
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value == "VTODO" => current = Some(Vec::new()),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) if value == "VTODO" => {
+                if let Some(properties) = current.take() {
+                    todos.push(todo_from_properties(&properties));
+                }
+            }
+            (_, Some(properties)) if nested == 0 => properties.push(property),
+            _ => {}
+        }
+    }
+
+    if todos.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    Ok(todos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::fixtures::{sample_todos, todo};
+
+    /// The shared sample, with text that needs escaping, a due time and a repeat counted
+    /// from completion.
+    fn sample() -> Vec<TodoResponse> {
+        let mut todos = sample_todos();
+        todos[0].description = Some("Call back; ask about the \"quote\",\nthen C:\\files".to_string());
+        todos[0].tags.push("a,b".to_string());
+        todos[0].recurrence = Some("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH".to_string());
+        todos[1].due_date = Some("2024-05-03T15:00:00+00:00".to_string());
+        todos[2].due_date = Some("2024-05-06".to_string());
+        todos[2].recurrence = Some("FREQ=DAILY;INTERVAL=3;X-FROM=COMPLETION".to_string());
+        todos.push(todo(4, "Someday"));
+        todos
+    }
+
+    fn same_instant(a: &str, b: &str) -> bool {
+        DateTime::parse_from_rfc3339(a).ok() == DateTime::parse_from_rfc3339(b).ok()
+    }
+
+    #[test]
+    fn exports_vtodos() {
+        let text = export_ics(&sample());
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(text.matches("BEGIN:VTODO").count(), 4);
+        assert!(text.contains("UID:todo-1@yew-boilerplate\r\n"));
+        assert!(text.contains("DTSTAMP:20240502T083000Z\r\n"));
+        assert!(text.contains("SUMMARY:Pack\\, then ship\r\n"));
+        assert!(text.contains("DUE;VALUE=DATE:20240503\r\n"));
+        assert!(text.contains("DUE:20240503T150000Z\r\n"));
+        assert!(text.contains("CATEGORIES:home,errands,a\\,b\r\n"));
+        assert!(text.contains("STATUS:COMPLETED\r\nPERCENT-COMPLETE:100\r\nCOMPLETED:20240502T083000Z\r\n"));
+        assert!(text.contains("STATUS:IN-PROCESS\r\n"));
+        assert!(text.contains("PRIORITY:1\r\n"));
+        assert!(text.contains("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH\r\n"));
+        assert!(text.contains("RRULE:FREQ=DAILY;INTERVAL=3\r\nX-REPEAT-FROM:COMPLETION\r\n"));
+        assert!(!text.contains("DTSTART"));
+        assert!(text.lines().all(|line| line.len() <= LINE_LIMIT));
+    }
+
+    #[test]
+    fn round_trip() {
+        let originals = sample();
+        let imported = import_ics(&export_ics(&originals)).unwrap();
+        assert_eq!(imported.len(), originals.len());
+
+        for (original, todo) in originals.iter().zip(&imported) {
+            assert_eq!(todo.title, original.title);
+            assert_eq!(todo.description, original.description);
+            assert_eq!(todo.status, Some(original.status()));
+            assert_eq!(todo.completed, original.completed);
+            assert_eq!(todo.priority, original.priority);
+            assert_eq!(todo.tags, original.tags);
+            assert_eq!(todo.recurrence, original.recurrence);
+            match (&todo.due_date, &original.due_date) {
+                (Some(due), Some(wanted)) if wanted.len() > 10 => assert!(same_instant(due, wanted)),
+                (due, wanted) => assert_eq!(due, wanted),
+            }
+            assert!(todo.checklist.is_empty());
+            assert_eq!(todo.list_id, None);
+        }
+    }
+
+    #[test]
+    fn folds_and_unfolds_long_lines() {
+        let title = "Ünïcödé ".repeat(20);
+        let text = export_ics(&[todo(1, title.trim())]);
+        let summary: Vec<&str> = text
+            .split("\r\n")
+            .skip_while(|line| !line.starts_with("SUMMARY:"))
+            .take_while(|line| !line.starts_with("STATUS:"))
+            .collect();
+        assert!(summary.len() > 1);
+        assert!(summary.iter().all(|line| line.len() <= LINE_LIMIT));
+        assert!(summary[1..].iter().all(|line| line.starts_with(' ')));
+
+        assert_eq!(import_ics(&text).unwrap()[0].title, title.trim());
+    }
+
+    #[test]
+    fn imports_from_other_calendars() {
+        let text = "BEGIN:VCALENDAR\n\
+                    VERSION:2.0\n\
+                    PRODID:-//Example//Tasks//EN\n\
+                    BEGIN:VEVENT\n\
+                    SUMMARY:Not a todo\n\
+                    END:VEVENT\n\
+                    BEGIN:VTODO\n\
+                    UID:abc\n\
+                    summary:Renew passport\n\
+                    DTSTART;VALUE=DATE:20240601\n\
+                    DUE;TZID=\"Europe/Berlin\":20240610T090000\n\
+                    PRIORITY:0\n\
+                    CATEGORIES:Admin\n\
+                    CATEGORIES:Travel,admin\n\
+                    BEGIN:VALARM\n\
+                    ACTION:DISPLAY\n\
+                    DESCRIPTION:Reminder\n\
+                    END:VALARM\n\
+                    END:VTODO\n\
+                    BEGIN:VTODO\n\
+                    SUMMARY:Old task\n\
+                    STATUS:CANCELLED\n\
+                    DTSTART:20240101T120000Z\n\
+                    RRULE:FREQ=YEARLY\n\
+                    END:VTODO\n\
+                    BEGIN:VTODO\n\
+                    SUMMARY:Half done\n\
+                    PERCENT-COMPLETE:50\n\
+                    PRIORITY:7\n\
+                    END:VTODO\n\
+                    END:VCALENDAR\n";
+        let imported = import_ics(text).unwrap();
+        assert_eq!(imported.len(), 3);
+
+        let passport = &imported[0];
+        assert_eq!(passport.title, "Renew passport");
+        // the alarm's description is not the todo's
+        assert_eq!(passport.description, None);
+        assert_eq!(passport.priority, Priority::Medium);
+        assert_eq!(passport.tags, vec!["Admin".to_string(), "Travel".to_string()]);
+        // DUE wins over DTSTART and keeps its wall-clock time
+        let due = passport.due_date.as_deref().unwrap();
+        assert!(due.starts_with("2024-06-10T09:00:00"));
+
+        let old = &imported[1];
+        assert_eq!(old.status, Some(Status::DONE));
+        assert!(old.due_date.is_some());
+        // not a schedule this app can repeat
+        assert_eq!(old.recurrence, None);
+
+        let half = &imported[2];
+        assert!(!half.completed);
+        assert_eq!(half.status, None);
+        assert_eq!(half.priority, Priority::Low);
+    }
+
+    #[test]
+    fn nothing_to_import() {
+        assert_eq!(import_ics(""), Err(ImportError::Empty));
+        assert_eq!(
+            import_ics("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:x\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"),
+            Err(ImportError::Empty)
+        );
+    }
+
+    #[test]
+    fn text_escapes() {
+        let text = "a\\b;c,d\ne";
+        assert_eq!(escape_text(text), "a\\\\b\\;c\\,d\\ne");
+        assert_eq!(unescape_text(&escape_text(text)), text);
+        assert_eq!(split_list("x\\,y, z"), vec!["x,y".to_string(), "z".to_string()]);
+    }
+}
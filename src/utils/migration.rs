@@ -0,0 +1,850 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Weekday};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::api::models::{parse_due_date, Priority, Todo};
+use crate::types::Status;
+use crate::utils::recurrence::{Recurrence, WEEKDAYS};
+use crate::utils::transfer::{add_tag, checklist_from, ImportError};
+
+/// Todo apps whose JSON exports can be migrated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MigrationSource {
+    /// A backup from the Sync API (`projects`, `items`, `labels`) or a REST task array.
+    Todoist,
+    /// A board exported as JSON ("Print and export" → "Export as JSON").
+    Trello,
+    /// Microsoft Graph `todoTask` JSON: lists with their `tasks`, or one list's `value`.
+    MicrosoftToDo,
+}
+
+impl MigrationSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MigrationSource::Todoist => "Todoist",
+            MigrationSource::Trello => "Trello",
+            MigrationSource::MicrosoftToDo => "Microsoft To Do",
+        }
+    }
+}
+
+/// Todos bound for one list; `name` is `None` for todos outside any list.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PlannedList {
+    pub name: Option<String>,
+    pub todos: Vec<Todo>,
+}
+
+/// What a migration would create, shown as a dry run before anything is sent.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MigrationPlan {
+    pub source: MigrationSource,
+    pub lists: Vec<PlannedList>,
+    /// Archived or deleted items left out.
+    pub skipped: usize,
+    /// Repeating items imported once, without their schedule.
+    pub repeats_dropped: usize,
+}
+
+impl MigrationPlan {
+    fn new(source: MigrationSource) -> Self {
+        MigrationPlan {
+            source,
+            lists: Vec::new(),
+            skipped: 0,
+            repeats_dropped: 0,
+        }
+    }
+
+    /// Adds `todo` to the list named `name`, ignoring case.
+    fn push(&mut self, name: Option<&str>, todo: Todo) {
+        let name = name.map(str::trim).filter(|name| !name.is_empty());
+        let existing = self.lists.iter_mut().find(|list| match (&list.name, name) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            (None, None) => true,
+            _ => false,
+        });
+        match existing {
+            Some(list) => list.todos.push(todo),
+            None => self.lists.push(PlannedList {
+                name: name.map(str::to_string),
+                todos: vec![todo],
+            }),
+        }
+    }
+
+    fn todos(&self) -> impl Iterator<Item = &Todo> {
+        self.lists.iter().flat_map(|list| list.todos.iter())
+    }
+
+    pub fn todo_count(&self) -> usize {
+        self.todos().count()
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.todos().filter(|todo| todo.completed).count()
+    }
+
+    pub fn due_count(&self) -> usize {
+        self.todos().filter(|todo| todo.due_date.is_some()).count()
+    }
+
+    pub fn checklist_count(&self) -> usize {
+        self.todos().map(|todo| todo.checklist.len()).sum()
+    }
+
+    /// Distinct tags, ignoring case.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.todos().flat_map(|todo| todo.tags.iter()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+}
+
+/// Ids are numbers in some API versions and strings in others.
+fn id_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(id) => id,
+        other => other.to_string(),
+    })
+}
+
+fn optional_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => None,
+        Some(Value::String(id)) => Some(id),
+        Some(other) => Some(other.to_string()),
+    })
+}
+
+/// A due date in the form todos store: `YYYY-MM-DD`, or RFC 3339 in the local
+/// timezone. Date-times without an offset are read as local time.
+fn due_from(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.len() == 10 {
+        return parse_due_date(value).map(|date| date.format("%Y-%m-%d").to_string());
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Local).to_rfc3339());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .and_then(|date| Local.from_local_datetime(&date).earliest())
+        .map(|date| date.to_rfc3339())
+}
+
+fn text(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+// Todoist
+
+#[derive(Deserialize)]
+struct TodoistBackup {
+    #[serde(default)]
+    projects: Vec<TodoistProject>,
+    items: Vec<TodoistTask>,
+    #[serde(default)]
+    labels: Vec<TodoistLabel>,
+}
+
+#[derive(Deserialize)]
+struct TodoistProject {
+    #[serde(deserialize_with = "id_string")]
+    id: String,
+    name: String,
+    #[serde(default)]
+    inbox_project: bool,
+}
+
+#[derive(Deserialize)]
+struct TodoistLabel {
+    #[serde(deserialize_with = "id_string")]
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct TodoistTask {
+    #[serde(deserialize_with = "id_string")]
+    id: String,
+    content: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default, deserialize_with = "optional_id")]
+    project_id: Option<String>,
+    #[serde(default, deserialize_with = "optional_id")]
+    parent_id: Option<String>,
+    /// Names, or label ids in older backups.
+    #[serde(default)]
+    labels: Vec<Value>,
+    /// 4 is the most urgent, 1 means none.
+    #[serde(default)]
+    priority: u8,
+    #[serde(default)]
+    due: Option<TodoistDue>,
+    #[serde(default, alias = "is_completed")]
+    checked: bool,
+    #[serde(default)]
+    is_deleted: bool,
+}
+
+#[derive(Deserialize)]
+struct TodoistDue {
+    #[serde(default)]
+    date: String,
+    #[serde(default)]
+    datetime: Option<String>,
+    #[serde(default)]
+    is_recurring: bool,
+}
+
+fn plan_todoist(backup: TodoistBackup) -> MigrationPlan {
+    let mut plan = MigrationPlan::new(MigrationSource::Todoist);
+    // the inbox is where todos outside any list go
+    let projects: HashMap<&str, Option<&str>> = backup
+        .projects
+        .iter()
+        .map(|project| (project.id.as_str(), (!project.inbox_project).then_some(project.name.as_str())))
+        .collect();
+    let labels: HashMap<&str, &str> = backup
+        .labels
+        .iter()
+        .map(|label| (label.id.as_str(), label.name.as_str()))
+        .collect();
+
+    let tasks: Vec<&TodoistTask> = backup
+        .items
+        .iter()
+        .filter(|task| !task.is_deleted)
+        .collect();
+    plan.skipped = backup.items.len() - tasks.len();
+    let parents: HashMap<&str, Option<&str>> = tasks
+        .iter()
+        .map(|task| (task.id.as_str(), task.parent_id.as_deref()))
+        .collect();
+    // sub-tasks at any depth become checklist items of their top-level task
+    let root_of = |task: &TodoistTask| {
+        let mut id = task.id.as_str();
+        for _ in 0..tasks.len() {
+            match parents.get(id).copied().flatten() {
+                Some(parent) if parents.contains_key(parent) => id = parent,
+                _ => break,
+            }
+        }
+        id.to_string()
+    };
+
+    let mut subtasks: HashMap<String, Vec<(String, bool)>> = HashMap::new();
+    for task in &tasks {
+        let root = root_of(task);
+        if root != task.id {
+            subtasks
+                .entry(root)
+                .or_default()
+                .push((task.content.clone(), task.checked));
+        }
+    }
+
+    for task in tasks.iter().filter(|task| root_of(task) == task.id) {
+        let mut tags = Vec::new();
+        for label in &task.labels {
+            match label {
+                Value::String(name) => {
+                    add_tag(&mut tags, labels.get(name.as_str()).copied().unwrap_or(name))
+                }
+                other => {
+                    if let Some(name) = labels.get(other.to_string().as_str()) {
+                        add_tag(&mut tags, name);
+                    }
+                }
+            }
+        }
+        if task.due.as_ref().is_some_and(|due| due.is_recurring) {
+            plan.repeats_dropped += 1;
+        }
+        let todo = Todo {
+            title: task.content.trim().to_string(),
+            completed: task.checked,
+            description: text(&task.description),
+            due_date: task
+                .due
+                .as_ref()
+                .and_then(|due| due_from(due.datetime.as_deref().unwrap_or(&due.date))),
+            priority: match task.priority {
+                4 => Priority::Urgent,
+                3 => Priority::High,
+                _ => Priority::Medium,
+            },
+            tags,
+            checklist: checklist_from(subtasks.remove(&task.id).unwrap_or_default()),
+            status: task.checked.then_some(Status::DONE),
+            ..Todo::default()
+        };
+        let list = task
+            .project_id
+            .as_deref()
+            .and_then(|id| projects.get(id).copied().flatten());
+        plan.push(list, todo);
+    }
+    plan
+}
+
+// Trello
+
+#[derive(Deserialize)]
+struct TrelloBoard {
+    name: String,
+    lists: Vec<TrelloList>,
+    cards: Vec<TrelloCard>,
+    #[serde(default)]
+    checklists: Vec<TrelloChecklist>,
+}
+
+#[derive(Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+struct TrelloCard {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(rename = "idList")]
+    id_list: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default, rename = "dueComplete")]
+    due_complete: bool,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+}
+
+#[derive(Deserialize)]
+struct TrelloLabel {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TrelloChecklist {
+    #[serde(rename = "idCard")]
+    id_card: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(default, rename = "checkItems")]
+    check_items: Vec<TrelloCheckItem>,
+}
+
+#[derive(Deserialize)]
+struct TrelloCheckItem {
+    name: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+/// Status meant by a Trello column name such as "Done" or "Doing".
+fn status_of_column(name: &str) -> Option<Status> {
+    let name = name.trim().to_lowercase();
+    if ["done", "complete", "completed", "finished", "closed"].contains(&name.as_str()) {
+        Some(Status::DONE)
+    } else if ["doing", "in progress", "wip", "in review", "review"].contains(&name.as_str()) {
+        Some(Status::IN_PROGRESS)
+    } else if ["to do", "todo", "backlog", "next", "ideas"].contains(&name.as_str()) {
+        Some(Status::PENDING)
+    } else {
+        None
+    }
+}
+
+/// The whole board becomes one list; columns become statuses, or tags when
+/// their name says nothing about progress.
+fn plan_trello(board: TrelloBoard) -> MigrationPlan {
+    let mut plan = MigrationPlan::new(MigrationSource::Trello);
+    let columns: HashMap<&str, &TrelloList> = board
+        .lists
+        .iter()
+        .map(|list| (list.id.as_str(), list))
+        .collect();
+
+    for card in &board.cards {
+        let column = columns.get(card.id_list.as_str());
+        if card.closed || column.is_some_and(|column| column.closed) {
+            plan.skipped += 1;
+            continue;
+        }
+        let column_status = column.and_then(|column| status_of_column(&column.name));
+        let status = if card.due_complete {
+            Status::DONE
+        } else {
+            column_status.unwrap_or_default()
+        };
+
+        let mut tags = Vec::new();
+        if let (Some(column), None) = (column, column_status) {
+            add_tag(&mut tags, &column.name.to_lowercase().replace(' ', "-"));
+        }
+        for label in &card.labels {
+            // unnamed labels are only a color
+            let name = match label.name.trim() {
+                "" => label.color.as_deref().unwrap_or(""),
+                name => name,
+            };
+            add_tag(&mut tags, name);
+        }
+
+        let mut checklists: Vec<&TrelloChecklist> = board
+            .checklists
+            .iter()
+            .filter(|checklist| checklist.id_card == card.id)
+            .collect();
+        checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        let items = checklists.into_iter().flat_map(|checklist| {
+            let mut items: Vec<&TrelloCheckItem> = checklist.check_items.iter().collect();
+            items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            items
+                .into_iter()
+                .map(|item| (item.name.clone(), item.state == "complete"))
+        });
+
+        let todo = Todo {
+            title: card.name.trim().to_string(),
+            completed: status == Status::DONE,
+            description: text(&card.desc),
+            due_date: card.due.as_deref().and_then(due_from),
+            tags,
+            checklist: checklist_from(items),
+            status: Some(status),
+            ..Todo::default()
+        };
+        plan.push(Some(&board.name), todo);
+    }
+    plan
+}
+
+// Microsoft To Do
+
+#[derive(Deserialize)]
+struct MsExport {
+    lists: Vec<MsList>,
+}
+
+#[derive(Deserialize)]
+struct MsList {
+    #[serde(rename = "displayName")]
+    display_name: String,
+    /// `defaultList` for the built-in "Tasks" list.
+    #[serde(default, rename = "wellknownListName")]
+    wellknown_list_name: String,
+    #[serde(default, alias = "value")]
+    tasks: Vec<MsTask>,
+}
+
+#[derive(Deserialize)]
+struct MsTaskPage {
+    value: Vec<MsTask>,
+}
+
+#[derive(Deserialize)]
+struct MsTask {
+    title: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    importance: String,
+    #[serde(default, rename = "dueDateTime")]
+    due_date_time: Option<MsDateTime>,
+    #[serde(default)]
+    body: Option<MsBody>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default, rename = "checklistItems")]
+    checklist_items: Vec<MsChecklistItem>,
+    #[serde(default)]
+    recurrence: Option<MsRecurrence>,
+}
+
+#[derive(Deserialize)]
+struct MsRecurrence {
+    pattern: MsPattern,
+}
+
+#[derive(Deserialize)]
+struct MsPattern {
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    interval: u32,
+    #[serde(default, rename = "daysOfWeek")]
+    days_of_week: Vec<String>,
+    #[serde(default, rename = "dayOfMonth")]
+    day_of_month: u32,
+}
+
+impl MsPattern {
+    /// The same schedule as a [`Recurrence`]; relative monthly ("second Tuesday") and
+    /// yearly patterns have no equivalent.
+    fn to_recurrence(&self) -> Option<Recurrence> {
+        let interval = self.interval.max(1);
+        match self.kind.as_str() {
+            "daily" => Some(Recurrence::daily(interval)),
+            "weekly" => {
+                let days = self
+                    .days_of_week
+                    .iter()
+                    .filter_map(|day| WEEKDAYS.into_iter().find(|weekday| day_name(*weekday) == day))
+                    .collect();
+                Some(Recurrence::weekly(interval, days))
+            }
+            "absoluteMonthly" if (1..=31).contains(&self.day_of_month) => {
+                Some(Recurrence::monthly(interval, self.day_of_month))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Graph's name for a weekday in `daysOfWeek`.
+fn day_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+#[derive(Deserialize)]
+struct MsDateTime {
+    #[serde(rename = "dateTime")]
+    date_time: String,
+}
+
+#[derive(Deserialize)]
+struct MsBody {
+    #[serde(default)]
+    content: String,
+    #[serde(default, rename = "contentType")]
+    content_type: String,
+}
+
+#[derive(Deserialize)]
+struct MsChecklistItem {
+    #[serde(rename = "displayName")]
+    display_name: String,
+    #[serde(default, rename = "isChecked")]
+    is_checked: bool,
+}
+
+/// Text of an HTML task note, without its tags.
+fn strip_html(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn plan_microsoft(lists: Vec<MsList>) -> MigrationPlan {
+    let mut plan = MigrationPlan::new(MigrationSource::MicrosoftToDo);
+    for list in lists {
+        // the built-in "Tasks" list holds todos outside any list
+        let name = (list.wellknown_list_name != "defaultList").then_some(list.display_name.as_str());
+        for task in list.tasks {
+            let recurrence = task.recurrence.and_then(|rule| {
+                let mapped = rule.pattern.to_recurrence();
+                if mapped.is_none() {
+                    plan.repeats_dropped += 1;
+                }
+                mapped
+            });
+            let status = match task.status.as_str() {
+                "completed" => Status::DONE,
+                "inProgress" | "waitingOnOthers" => Status::IN_PROGRESS,
+                _ => Status::PENDING,
+            };
+            let description = task.body.and_then(|body| match body.content_type.as_str() {
+                "html" => text(&strip_html(&body.content)),
+                _ => text(&body.content),
+            });
+            let mut tags = Vec::new();
+            for category in &task.categories {
+                add_tag(&mut tags, category);
+            }
+            let todo = Todo {
+                title: task.title.trim().to_string(),
+                completed: status == Status::DONE,
+                description,
+                // To Do due dates are whole days stored as midnight
+                due_date: task
+                    .due_date_time
+                    .and_then(|due| parse_due_date(&due.date_time))
+                    .map(|date| date.format("%Y-%m-%d").to_string()),
+                priority: match task.importance.as_str() {
+                    "high" => Priority::High,
+                    "low" => Priority::Low,
+                    _ => Priority::Medium,
+                },
+                tags,
+                checklist: checklist_from(
+                    task.checklist_items
+                        .into_iter()
+                        .map(|item| (item.display_name, item.is_checked)),
+                ),
+                status: Some(status),
+                recurrence: recurrence.map(|rule| rule.to_rule()),
+                ..Todo::default()
+            };
+            plan.push(name, todo);
+        }
+    }
+    plan
+}
+
+/// Reads an export from one of the [`MigrationSource`]s, telling them apart by shape.
+pub fn plan_migration(text: &str) -> Result<MigrationPlan, ImportError> {
+    let text = text.trim_start_matches('\u{feff}');
+    let value: Value = serde_json::from_str(text).map_err(|err| ImportError::Json(err.to_string()))?;
+    let has = |key: &str| value.get(key).is_some();
+    let first_has = |key: &str| {
+        value
+            .as_array()
+            .and_then(|items| items.first())
+            .is_some_and(|item| item.get(key).is_some())
+    };
+    let unknown = || ImportError::Json("not an export from Todoist, Trello or Microsoft To Do".to_string());
+    let invalid = |err: serde_json::Error| ImportError::Json(err.to_string());
+
+    let plan = if has("cards") && has("lists") {
+        plan_trello(serde_json::from_value(value).map_err(invalid)?)
+    } else if has("items") {
+        plan_todoist(serde_json::from_value(value).map_err(invalid)?)
+    } else if first_has("content") {
+        let items: Vec<TodoistTask> = serde_json::from_value(value).map_err(invalid)?;
+        plan_todoist(TodoistBackup {
+            projects: Vec::new(),
+            items,
+            labels: Vec::new(),
+        })
+    } else if has("lists") {
+        plan_microsoft(serde_json::from_value::<MsExport>(value).map_err(invalid)?.lists)
+    } else if first_has("displayName") {
+        plan_microsoft(serde_json::from_value(value).map_err(invalid)?)
+    } else if has("value") {
+        let page: MsTaskPage = serde_json::from_value(value).map_err(invalid)?;
+        plan_microsoft(vec![MsList {
+            display_name: String::new(),
+            wellknown_list_name: "defaultList".to_string(),
+            tasks: page.value,
+        }])
+    } else {
+        return Err(unknown());
+    };
+
+    if plan.lists.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list<'a>(plan: &'a MigrationPlan, name: Option<&str>) -> &'a PlannedList {
+        plan.lists
+            .iter()
+            .find(|list| list.name.as_deref() == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn todoist_backup() {
+        let text = r#"{
+            "projects": [
+                {"id": "1", "name": "Inbox", "inbox_project": true},
+                {"id": "2", "name": "Home"}
+            ],
+            "labels": [{"id": 7, "name": "errands"}],
+            "items": [
+                {"id": "10", "content": "Buy paint", "project_id": "2", "labels": ["errands"], "priority": 4,
+                 "due": {"date": "2024-05-03", "is_recurring": false}},
+                {"id": "11", "content": "White", "project_id": "2", "parent_id": "10", "checked": true},
+                {"id": "12", "content": "Brushes", "project_id": "2", "parent_id": "11"},
+                {"id": "13", "content": "Call bank", "project_id": "1", "labels": [7], "priority": 1,
+                 "description": "about the card", "due": {"date": "2024-05-06T09:30:00", "is_recurring": true}},
+                {"id": "14", "content": "Gone", "project_id": "1", "is_deleted": true}
+            ]
+        }"#;
+        let plan = plan_migration(text).unwrap();
+        assert_eq!(plan.source, MigrationSource::Todoist);
+        assert_eq!(plan.todo_count(), 2);
+        assert_eq!(plan.skipped, 1);
+        assert_eq!(plan.repeats_dropped, 1);
+
+        let paint = &list(&plan, Some("Home")).todos[0];
+        assert_eq!(paint.title, "Buy paint");
+        assert_eq!(paint.priority, Priority::Urgent);
+        assert_eq!(paint.tags, vec!["errands".to_string()]);
+        assert_eq!(paint.due_date.as_deref(), Some("2024-05-03"));
+        // nested sub-tasks flatten onto the top-level task
+        let items: Vec<(&str, bool)> = paint.checklist.iter().map(|item| (item.title.as_str(), item.done)).collect();
+        assert_eq!(items, vec![("White", true), ("Brushes", false)]);
+
+        // the inbox is no list at all
+        let bank = &list(&plan, None).todos[0];
+        assert_eq!(bank.priority, Priority::Medium);
+        assert_eq!(bank.tags, vec!["errands".to_string()]);
+        assert_eq!(bank.description.as_deref(), Some("about the card"));
+        assert!(bank.due_date.as_deref().unwrap().starts_with("2024-05-06T09:30:00"));
+    }
+
+    #[test]
+    fn todoist_rest_tasks() {
+        let text = r#"[{"id": 1, "content": "Ship it", "is_completed": true, "project_id": 99}]"#;
+        let plan = plan_migration(text).unwrap();
+        let todo = &list(&plan, None).todos[0];
+        assert!(todo.completed);
+        assert_eq!(todo.status, Some(Status::DONE));
+    }
+
+    #[test]
+    fn trello_board() {
+        let text = r#"{
+            "name": "Website",
+            "lists": [
+                {"id": "a", "name": "Ideas"},
+                {"id": "b", "name": "Doing"},
+                {"id": "c", "name": "Launch week"},
+                {"id": "d", "name": "Old", "closed": true}
+            ],
+            "cards": [
+                {"id": "1", "name": "Blog", "idList": "a", "labels": [{"name": "content"}, {"name": "", "color": "red"}]},
+                {"id": "2", "name": "Header", "idList": "b", "desc": "new logo", "due": "2024-05-03T10:00:00.000Z"},
+                {"id": "3", "name": "Announce", "idList": "c", "dueComplete": true},
+                {"id": "4", "name": "Archived", "idList": "a", "closed": true},
+                {"id": "5", "name": "In closed list", "idList": "d"}
+            ],
+            "checklists": [
+                {"idCard": "2", "pos": 2, "checkItems": [{"name": "Colors", "state": "incomplete", "pos": 1}]},
+                {"idCard": "2", "pos": 1, "checkItems": [
+                    {"name": "Sketch", "state": "complete", "pos": 2},
+                    {"name": "Brief", "state": "complete", "pos": 1}
+                ]}
+            ]
+        }"#;
+        let plan = plan_migration(text).unwrap();
+        assert_eq!(plan.source, MigrationSource::Trello);
+        assert_eq!(plan.skipped, 2);
+        let todos = &list(&plan, Some("Website")).todos;
+        assert_eq!(todos.len(), 3);
+
+        assert_eq!(todos[0].status, Some(Status::PENDING));
+        assert_eq!(todos[0].tags, vec!["content".to_string(), "red".to_string()]);
+
+        assert_eq!(todos[1].status, Some(Status::IN_PROGRESS));
+        assert_eq!(todos[1].description.as_deref(), Some("new logo"));
+        assert!(todos[1].due_date.is_some());
+        let items: Vec<&str> = todos[1].checklist.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(items, vec!["Brief", "Sketch", "Colors"]);
+
+        // a column that is not a status is kept as a tag
+        assert!(todos[2].completed);
+        assert_eq!(todos[2].tags, vec!["launch-week".to_string()]);
+    }
+
+    #[test]
+    fn microsoft_to_do_lists() {
+        let text = r#"{"lists": [
+            {"displayName": "Tasks", "wellknownListName": "defaultList", "tasks": [
+                {"title": "Renew passport", "importance": "high", "status": "notStarted",
+                 "dueDateTime": {"dateTime": "2024-06-10T00:00:00.0000000", "timeZone": "UTC"},
+                 "body": {"content": "<p>Bring&nbsp;photos</p>", "contentType": "html"},
+                 "categories": ["Admin"]}
+            ]},
+            {"displayName": "Groceries", "value": [
+                {"title": "Milk", "status": "completed", "importance": "low",
+                 "checklistItems": [{"displayName": "Oat", "isChecked": true}],
+                 "recurrence": {"pattern": {"type": "weekly", "interval": 2,
+                                            "daysOfWeek": ["monday", "thursday"]}}},
+                {"title": "Water plants", "recurrence": {"pattern": {"type": "daily", "interval": 3}}},
+                {"title": "Pay rent", "recurrence": {"pattern": {"type": "absoluteMonthly", "interval": 1,
+                                                                 "dayOfMonth": 31}}},
+                {"title": "Book club", "recurrence": {"pattern": {"type": "relativeMonthly", "interval": 1,
+                                                                  "daysOfWeek": ["tuesday"], "index": "second"}}}
+            ]}
+        ]}"#;
+        let plan = plan_migration(text).unwrap();
+        assert_eq!(plan.source, MigrationSource::MicrosoftToDo);
+        assert_eq!(plan.repeats_dropped, 1);
+
+        let repeats: Vec<Option<&str>> = list(&plan, Some("Groceries"))
+            .todos
+            .iter()
+            .map(|todo| todo.recurrence.as_deref())
+            .collect();
+        assert_eq!(
+            repeats,
+            vec![
+                Some("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH"),
+                Some("FREQ=DAILY;INTERVAL=3"),
+                Some("FREQ=MONTHLY;INTERVAL=1;BYMONTHDAY=31"),
+                None,
+            ]
+        );
+        assert_eq!(plan.tags(), vec!["Admin".to_string()]);
+        assert_eq!(plan.due_count(), 1);
+        assert_eq!(plan.completed_count(), 1);
+        assert_eq!(plan.checklist_count(), 1);
+
+        let passport = &list(&plan, None).todos[0];
+        assert_eq!(passport.priority, Priority::High);
+        assert_eq!(passport.due_date.as_deref(), Some("2024-06-10"));
+        assert_eq!(passport.description.as_deref(), Some("Bring photos"));
+
+        let milk = &list(&plan, Some("Groceries")).todos[0];
+        assert!(milk.completed);
+        assert_eq!(milk.priority, Priority::Low);
+    }
+
+    #[test]
+    fn microsoft_graph_page_and_unknown_files() {
+        let plan = plan_migration(r#"{"value": [{"title": "One"}]}"#).unwrap();
+        assert_eq!(plan.source, MigrationSource::MicrosoftToDo);
+        assert_eq!(list(&plan, None).todos[0].title, "One");
+
+        assert!(matches!(plan_migration(r#"[{"title": "ours"}]"#), Err(ImportError::Json(_))));
+        assert!(matches!(plan_migration("not json"), Err(ImportError::Json(_))));
+        assert_eq!(plan_migration(r#"{"items": []}"#), Err(ImportError::Empty));
+    }
+}
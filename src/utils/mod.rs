@@ -1,3 +1,13 @@
+pub mod dates;
+pub mod fuzzy;
+pub mod ical;
+pub mod migration;
+pub mod quick_add;
+pub mod recurrence;
+pub mod storage;
+pub mod transfer;
+pub mod todo_query;
+
 /// Returns the backend origin provided at build time via the `BACKEND_URL` env var.
 /// Falls back to `http://127.0.0.1:8080` for local development.
 pub fn backend_origin() -> &'static str {
@@ -10,3 +20,17 @@ pub fn api_url(path: &str) -> String {
     let normalized_path = path.trim_start_matches('/');
     format!("{}/{}", origin, normalized_path)
 }
+
+/// Whether the live todo channel is enabled at build time via `LIVE_UPDATES=true`.
+pub fn live_updates_enabled() -> bool {
+    matches!(option_env!("LIVE_UPDATES"), Some("1" | "true" | "yes"))
+}
+
+/// Like [`api_url`], but with the `ws`/`wss` scheme matching the backend origin.
+pub fn ws_url(path: &str) -> String {
+    let url = api_url(path);
+    match url.strip_prefix("http") {
+        Some(rest) => format!("ws{}", rest),
+        None => url,
+    }
+}
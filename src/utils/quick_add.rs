@@ -0,0 +1,491 @@
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::api::models::{format_due, Priority};
+use crate::utils::dates::{add_months, month_start, week_start};
+use crate::utils::recurrence::WEEKDAYS;
+
+/// Time given to "tonight" when no time is typed.
+const TONIGHT_HOUR: u32 = 20;
+
+/// Fields picked out of a quick-add line such as
+/// `Call mom tomorrow 5pm !high #family @home`.
+///
+/// - dates: `today`, `tonight`, `tomorrow`, weekday names (`friday`, `next fri`),
+///   `next week`/`next month`, `in 3 days`/`in 2 weeks`, `may 3`/`3rd may`, `2024-05-03`,
+///   optionally after `on`, `by` or `due`
+/// - times: `5pm`, `5:30pm`, `5 pm`, `17:00`, `noon`, `midnight`, optionally after `at`
+/// - priorities: `!low`, `!medium`, `!high`, `!urgent`, or `p1` (urgent) to `p4` (low)
+/// - tags: `#work`; lists: `@home`, with `-` or `_` standing for spaces
+///
+/// Only the first date and time are taken; a word starting with `\` is kept
+/// literally (`\friday`). Everything else, in order, is the title.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct QuickAdd {
+    pub title: String,
+    pub due_date: Option<NaiveDate>,
+    pub due_time: Option<NaiveTime>,
+    pub priority: Option<Priority>,
+    pub tags: Vec<String>,
+    /// List name as typed, without the `@`.
+    pub list: Option<String>,
+}
+
+impl QuickAdd {
+    /// Parses `input` with relative dates resolved against `now`.
+    pub fn parse(input: &str, now: NaiveDateTime) -> Self {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let mut parsed = QuickAdd::default();
+        let mut title: Vec<String> = Vec::new();
+        let mut date: Option<NaiveDate> = None;
+        let mut time: Option<NaiveTime> = None;
+        // from "tonight", unless a time is typed as well
+        let mut implied_time: Option<NaiveTime> = None;
+
+        let mut index = 0;
+        while index < words.len() {
+            let word = words[index];
+            if let Some(literal) = word.strip_prefix('\\').filter(|rest| !rest.is_empty()) {
+                title.push(literal.to_string());
+                index += 1;
+                continue;
+            }
+            if let Some(tag) = word.strip_prefix('#').map(trim_punctuation).filter(|tag| !tag.is_empty()) {
+                if !parsed.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    parsed.tags.push(tag.to_string());
+                }
+                index += 1;
+                continue;
+            }
+            if let Some(list) = word.strip_prefix('@').map(trim_punctuation).filter(|list| !list.is_empty()) {
+                parsed.list = Some(list.replace(['-', '_'], " "));
+                index += 1;
+                continue;
+            }
+            if let Some(priority) = parse_priority(word) {
+                parsed.priority = Some(priority);
+                index += 1;
+                continue;
+            }
+
+            let rest: Vec<String> = words[index..].iter().map(|word| normalize(word)).collect();
+            if date.is_none() {
+                if let Some((found, found_time, used)) = match_date(&rest, now.date()) {
+                    date = Some(found);
+                    implied_time = found_time;
+                    index += used;
+                    continue;
+                }
+            }
+            if time.is_none() {
+                if let Some((found, used)) = match_time(&rest) {
+                    time = Some(found);
+                    index += used;
+                    continue;
+                }
+            }
+            title.push(word.to_string());
+            index += 1;
+        }
+
+        time = time.or(implied_time);
+        // a time alone means its next occurrence
+        if date.is_none() {
+            date = time.map(|time| {
+                if time > now.time() {
+                    now.date()
+                } else {
+                    now.date() + Days::new(1)
+                }
+            });
+        }
+
+        parsed.title = title.join(" ");
+        parsed.due_date = date;
+        parsed.due_time = time;
+        parsed
+    }
+
+    /// Whether anything besides the title was recognised.
+    pub fn has_fields(&self) -> bool {
+        self.due_date.is_some() || self.priority.is_some() || !self.tags.is_empty() || self.list.is_some()
+    }
+
+    /// Due date as stored on a todo, see [`format_due`].
+    pub fn due_string(&self) -> Option<String> {
+        self.due_date.map(|date| format_due(date, self.due_time))
+    }
+
+    /// Due date for the preview, e.g. "Fri, May 3 17:00".
+    pub fn describe_due(&self) -> Option<String> {
+        let date = self.due_date?;
+        let day = date.format("%a, %b %-d").to_string();
+        Some(match self.due_time {
+            Some(time) => format!("{} {}", day, time.format("%H:%M")),
+            None => day,
+        })
+    }
+}
+
+fn trim_punctuation(word: &str) -> &str {
+    word.trim_end_matches([',', '.', ';', ':', '!', '?'])
+}
+
+fn normalize(word: &str) -> String {
+    trim_punctuation(word).to_lowercase()
+}
+
+fn parse_priority(word: &str) -> Option<Priority> {
+    let word = normalize(word);
+    let level = word.strip_prefix('!').or_else(|| word.strip_prefix('p').filter(|rest| rest.len() == 1))?;
+    match level {
+        "1" | "urgent" => Some(Priority::Urgent),
+        "2" | "high" => Some(Priority::High),
+        "3" | "medium" | "med" => Some(Priority::Medium),
+        "4" | "low" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// A full weekday name, or with `short` also "fri", "thurs" or "tues"; short forms
+/// on their own are ordinary words too often ("sun cream", "sat down").
+fn parse_weekday(word: &str, short: bool) -> Option<Weekday> {
+    WEEKDAYS.into_iter().find(|day| {
+        let name = match day {
+            Weekday::Mon => "monday",
+            Weekday::Tue => "tuesday",
+            Weekday::Wed => "wednesday",
+            Weekday::Thu => "thursday",
+            Weekday::Fri => "friday",
+            Weekday::Sat => "saturday",
+            Weekday::Sun => "sunday",
+        };
+        word == name || (short && word.len() >= 3 && name.starts_with(word))
+    })
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june", "july", "august", "september",
+        "october", "november", "december",
+    ];
+    MONTHS
+        .iter()
+        .position(|name| word.len() >= 3 && name.starts_with(word))
+        .map(|index| index as u32 + 1)
+}
+
+/// Day of month such as `3`, `3rd` or `21st`.
+fn parse_day(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+fn parse_count(word: &str) -> Option<u64> {
+    match word {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        _ => word.parse().ok().filter(|count| *count > 0),
+    }
+}
+
+/// Next `weekday` on or after `from`.
+fn upcoming(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    from + Days::new(ahead as u64)
+}
+
+/// `month`/`day` this year, or next year once it has passed.
+fn next_month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), month, day);
+    match this_year {
+        Some(date) if date >= today => Some(date),
+        _ => NaiveDate::from_ymd_opt(today.year() + 1, month, day),
+    }
+}
+
+/// Date phrase at the start of `words`, with the time it implies and the
+/// number of words used.
+fn match_date(words: &[String], today: NaiveDate) -> Option<(NaiveDate, Option<NaiveTime>, usize)> {
+    match_date_after(words, today, false)
+}
+
+/// [`match_date`], where `marked` tells that an "on", "by" or "due" came first.
+fn match_date_after(
+    words: &[String],
+    today: NaiveDate,
+    marked: bool,
+) -> Option<(NaiveDate, Option<NaiveTime>, usize)> {
+    let word = |index: usize| words.get(index).map(String::as_str).unwrap_or("");
+
+    if matches!(word(0), "on" | "by" | "due") {
+        return match_date_after(&words[1..], today, true)
+            .map(|(date, time, used)| (date, time, used + 1));
+    }
+
+    let found = match (word(0), word(1)) {
+        ("today", _) => (today, None, 1),
+        ("tonight", _) => (today, NaiveTime::from_hms_opt(TONIGHT_HOUR, 0, 0), 1),
+        ("tomorrow" | "tmrw" | "tmr", _) => (today + Days::new(1), None, 1),
+        ("next", "week") => (week_start(today) + Days::new(7), None, 2),
+        ("next", "month") => (add_months(month_start(today), 1), None, 2),
+        ("next", day) if parse_weekday(day, true).is_some() => {
+            let weekday = parse_weekday(day, true)?;
+            let next_week = week_start(today) + Days::new(7);
+            (upcoming(next_week, weekday), None, 2)
+        }
+        ("this", day) if parse_weekday(day, true).is_some() => {
+            (upcoming(today, parse_weekday(day, true)?), None, 2)
+        }
+        (day, _) if parse_weekday(day, marked).is_some() => {
+            (upcoming(today, parse_weekday(day, marked)?), None, 1)
+        }
+        ("in", count) if parse_count(count).is_some() => {
+            let count = parse_count(count)?;
+            let date = match word(2).trim_end_matches('s') {
+                "day" => today + Days::new(count),
+                "week" => today + Days::new(7 * count),
+                "month" => add_months(today, count as i32),
+                _ => return None,
+            };
+            (date, None, 3)
+        }
+        (month, day) if parse_month(month).is_some() && parse_day(day).is_some() => {
+            (next_month_day(today, parse_month(month)?, parse_day(day)?)?, None, 2)
+        }
+        (day, month) if parse_day(day).is_some() && parse_month(month).is_some() => {
+            (next_month_day(today, parse_month(month)?, parse_day(day)?)?, None, 2)
+        }
+        (iso, _) => (NaiveDate::parse_from_str(iso, "%Y-%m-%d").ok()?, None, 1),
+    };
+    Some(found)
+}
+
+/// Time phrase at the start of `words` and the number of words used.
+fn match_time(words: &[String]) -> Option<(NaiveTime, usize)> {
+    let word = |index: usize| words.get(index).map(String::as_str).unwrap_or("");
+
+    if word(0) == "at" {
+        return match_time(&words[1..]).map(|(time, used)| (time, used + 1));
+    }
+    match word(0) {
+        "noon" => return Some((NaiveTime::from_hms_opt(12, 0, 0)?, 1)),
+        "midnight" => return Some((NaiveTime::from_hms_opt(0, 0, 0)?, 1)),
+        _ => {}
+    }
+
+    // "5pm" or "5 pm"
+    let (clock, meridiem, used) = match (word(0), word(1)) {
+        (clock, "am" | "pm") => (clock, word(1), 2),
+        (clock, _) if clock.ends_with("am") || clock.ends_with("pm") => {
+            let (clock, meridiem) = clock.split_at(clock.len() - 2);
+            (clock, meridiem, 1)
+        }
+        (clock, _) => (clock, "", 1),
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        // a bare number is only a time with am/pm
+        None if !meridiem.is_empty() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match meridiem {
+        "" if hour < 24 => hour,
+        "am" if (1..=12).contains(&hour) => hour % 12,
+        "pm" if (1..=12).contains(&hour) => hour % 12 + 12,
+        _ => return None,
+    };
+    Some((NaiveTime::from_hms_opt(hour, minute, 0)?, used))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday, May 1 2024, 10:00.
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2024, month, day)
+    }
+
+    fn time(hour: u32, minute: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(hour, minute, 0)
+    }
+
+    #[test]
+    fn plain_text_is_only_a_title() {
+        let parsed = QuickAdd::parse("  Buy   milk ", now());
+        assert_eq!(parsed.title, "Buy milk");
+        assert!(!parsed.has_fields());
+        assert_eq!(parsed.due_string(), None);
+    }
+
+    #[test]
+    fn extracts_every_kind_of_field() {
+        let parsed = QuickAdd::parse("Call mom tomorrow 5pm !high #family @home", now());
+        assert_eq!(parsed.title, "Call mom");
+        assert_eq!(parsed.due_date, date(5, 2));
+        assert_eq!(parsed.due_time, time(17, 0));
+        assert_eq!(parsed.priority, Some(Priority::High));
+        assert_eq!(parsed.tags, vec!["family".to_string()]);
+        assert_eq!(parsed.list.as_deref(), Some("home"));
+        assert!(parsed.has_fields());
+    }
+
+    #[test]
+    fn relative_days() {
+        assert_eq!(QuickAdd::parse("x today", now()).due_date, date(5, 1));
+        assert_eq!(QuickAdd::parse("x tmrw", now()).due_date, date(5, 2));
+        assert_eq!(QuickAdd::parse("x in 3 days", now()).due_date, date(5, 4));
+        assert_eq!(QuickAdd::parse("x in a week", now()).due_date, date(5, 8));
+        assert_eq!(QuickAdd::parse("x in 2 months", now()).due_date, date(7, 1));
+        assert_eq!(QuickAdd::parse("x next week", now()).due_date, date(5, 6));
+        assert_eq!(QuickAdd::parse("x next month", now()).due_date, date(6, 1));
+
+        let tonight = QuickAdd::parse("x tonight", now());
+        assert_eq!(tonight.due_date, date(5, 1));
+        assert_eq!(tonight.due_time, time(20, 0));
+        assert_eq!(QuickAdd::parse("x tonight 9pm", now()).due_time, time(21, 0));
+    }
+
+    #[test]
+    fn weekdays() {
+        // the coming one, today included
+        assert_eq!(QuickAdd::parse("x friday", now()).due_date, date(5, 3));
+        assert_eq!(QuickAdd::parse("x on wed", now()).due_date, date(5, 1));
+        assert_eq!(QuickAdd::parse("x on Mon", now()).due_date, date(5, 6));
+        assert_eq!(QuickAdd::parse("x this thurs", now()).due_date, date(5, 2));
+        // the one in the following week
+        assert_eq!(QuickAdd::parse("x next friday", now()).due_date, date(5, 10));
+        assert_eq!(QuickAdd::parse("x next monday", now()).due_date, date(5, 6));
+    }
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(QuickAdd::parse("x may 3", now()).due_date, date(5, 3));
+        assert_eq!(QuickAdd::parse("x 21st June", now()).due_date, date(6, 21));
+        assert_eq!(QuickAdd::parse("x by 2024-08-15", now()).due_date, date(8, 15));
+        // already passed this year
+        assert_eq!(
+            QuickAdd::parse("x jan 15", now()).due_date,
+            NaiveDate::from_ymd_opt(2025, 1, 15)
+        );
+        // not a date, so part of the title
+        let parsed = QuickAdd::parse("x feb 30", now());
+        assert_eq!(parsed.due_date, None);
+        assert_eq!(parsed.title, "x feb 30");
+    }
+
+    #[test]
+    fn times() {
+        let parsed = QuickAdd::parse("Standup at 9:30am friday", now());
+        assert_eq!(parsed.title, "Standup");
+        assert_eq!(parsed.due_date, date(5, 3));
+        assert_eq!(parsed.due_time, time(9, 30));
+
+        assert_eq!(QuickAdd::parse("x tomorrow 5 pm", now()).due_time, time(17, 0));
+        assert_eq!(QuickAdd::parse("x tomorrow 17:45", now()).due_time, time(17, 45));
+        assert_eq!(QuickAdd::parse("x tomorrow at noon", now()).due_time, time(12, 0));
+        assert_eq!(QuickAdd::parse("x 12am tomorrow", now()).due_time, time(0, 0));
+
+        // a time alone is today while still ahead, otherwise tomorrow
+        assert_eq!(QuickAdd::parse("x 3pm", now()).due_date, date(5, 1));
+        assert_eq!(QuickAdd::parse("x 8am", now()).due_date, date(5, 2));
+
+        // bare numbers and impossible times stay in the title
+        assert_eq!(QuickAdd::parse("Read 5 books", now()).title, "Read 5 books");
+        assert_eq!(QuickAdd::parse("x 13pm", now()).title, "x 13pm");
+        assert_eq!(QuickAdd::parse("x 25:00", now()).title, "x 25:00");
+    }
+
+    #[test]
+    fn only_the_first_date_is_taken() {
+        let parsed = QuickAdd::parse("Move meeting from monday to friday", now());
+        assert_eq!(parsed.due_date, date(5, 6));
+        assert_eq!(parsed.title, "Move meeting from to friday");
+    }
+
+    #[test]
+    fn short_weekdays_need_a_marker() {
+        let parsed = QuickAdd::parse("Buy sun cream", now());
+        assert_eq!(parsed.title, "Buy sun cream");
+        assert_eq!(parsed.due_date, None);
+        assert_eq!(QuickAdd::parse("Sat down with Ana", now()).title, "Sat down with Ana");
+
+        let parsed = QuickAdd::parse("Buy sun cream by sat", now());
+        assert_eq!(parsed.title, "Buy sun cream");
+        assert_eq!(parsed.due_date, date(5, 4));
+        assert_eq!(QuickAdd::parse("x sunday", now()).due_date, date(5, 5));
+    }
+
+    #[test]
+    fn priorities() {
+        assert_eq!(QuickAdd::parse("x p1", now()).priority, Some(Priority::Urgent));
+        assert_eq!(QuickAdd::parse("x P2", now()).priority, Some(Priority::High));
+        assert_eq!(QuickAdd::parse("x !med", now()).priority, Some(Priority::Medium));
+        assert_eq!(QuickAdd::parse("x !4", now()).priority, Some(Priority::Low));
+        assert_eq!(QuickAdd::parse("x !urgent", now()).priority, Some(Priority::Urgent));
+
+        let parsed = QuickAdd::parse("Ship p5 !soon", now());
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.title, "Ship p5 !soon");
+    }
+
+    #[test]
+    fn tags_and_lists() {
+        let parsed = QuickAdd::parse("x #Work #work, #urgent @side-projects @Home_Office", now());
+        assert_eq!(parsed.tags, vec!["Work".to_string(), "urgent".to_string()]);
+        // the last list wins
+        assert_eq!(parsed.list.as_deref(), Some("Home Office"));
+
+        // lone markers and addresses are text
+        let parsed = QuickAdd::parse("Email bob@example.com # @", now());
+        assert_eq!(parsed.title, "Email bob@example.com # @");
+        assert!(!parsed.has_fields());
+    }
+
+    #[test]
+    fn escaped_words_stay_in_the_title() {
+        let parsed = QuickAdd::parse(r"Read \Friday newsletter \#1 tomorrow", now());
+        assert_eq!(parsed.title, "Read Friday newsletter #1");
+        assert_eq!(parsed.due_date, date(5, 2));
+        assert!(parsed.tags.is_empty());
+    }
+
+    #[test]
+    fn prepositions_without_a_date_stay() {
+        let parsed = QuickAdd::parse("Work on the report due soon at home", now());
+        assert_eq!(parsed.title, "Work on the report due soon at home");
+        assert_eq!(parsed.due_date, None);
+    }
+
+    #[test]
+    fn trailing_punctuation_is_ignored() {
+        let parsed = QuickAdd::parse("Pay rent tomorrow, #bills.", now());
+        assert_eq!(parsed.title, "Pay rent");
+        assert_eq!(parsed.due_date, date(5, 2));
+        assert_eq!(parsed.tags, vec!["bills".to_string()]);
+    }
+
+    #[test]
+    fn due_string_and_description() {
+        let parsed = QuickAdd::parse("x friday", now());
+        assert_eq!(parsed.due_string().as_deref(), Some("2024-05-03"));
+        assert_eq!(parsed.describe_due().as_deref(), Some("Fri, May 3"));
+
+        let parsed = QuickAdd::parse("x friday 5pm", now());
+        // the local offset varies; the date and time do not
+        assert!(parsed.due_string().unwrap().starts_with("2024-05-03T17:00:00"));
+        assert_eq!(parsed.describe_due().as_deref(), Some("Fri, May 3 17:00"));
+    }
+}
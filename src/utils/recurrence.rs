@@ -0,0 +1,338 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::utils::dates::{add_months, month_start, week_start};
+
+/// Upper bound on the dates scanned while looking for the next occurrence.
+const MAX_STEPS: usize = 3660;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        }
+    }
+}
+
+/// Repeat schedule of a todo, stored as an RRULE-style string such as
+/// `FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,TH` or `FREQ=MONTHLY;BYMONTHDAY=15`.
+///
+/// `X-FROM=COMPLETION` counts the interval from the day the todo was completed
+/// instead of from its due date ("every 3 days after I last did it").
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Every `interval` days, weeks or months; at least 1.
+    pub interval: u32,
+    /// Days of a weekly rule; empty means the weekday of the due date.
+    pub weekdays: Vec<Weekday>,
+    /// Day of a monthly rule, clamped to the end of shorter months.
+    pub month_day: Option<u32>,
+    pub from_completion: bool,
+}
+
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    (add_months(month_start(date), 1) - Days::new(1)).day()
+}
+
+impl Recurrence {
+    pub fn daily(interval: u32) -> Self {
+        Recurrence {
+            frequency: Frequency::Daily,
+            interval: interval.max(1),
+            weekdays: Vec::new(),
+            month_day: None,
+            from_completion: false,
+        }
+    }
+
+    pub fn weekdays() -> Self {
+        Recurrence {
+            frequency: Frequency::Weekly,
+            weekdays: WEEKDAYS[..5].to_vec(),
+            ..Recurrence::daily(1)
+        }
+    }
+
+    pub fn weekly(interval: u32, weekdays: Vec<Weekday>) -> Self {
+        Recurrence {
+            frequency: Frequency::Weekly,
+            weekdays,
+            ..Recurrence::daily(interval)
+        }
+    }
+
+    pub fn monthly(interval: u32, month_day: u32) -> Self {
+        Recurrence {
+            frequency: Frequency::Monthly,
+            month_day: Some(month_day.clamp(1, 31)),
+            ..Recurrence::daily(interval)
+        }
+    }
+
+    pub fn after_completion(days: u32) -> Self {
+        Recurrence {
+            from_completion: true,
+            ..Recurrence::daily(days)
+        }
+    }
+
+    /// Parses a rule; unknown parts are ignored, a missing `FREQ` yields `None`.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut recurrence = Recurrence::daily(1);
+        for part in rule.trim().trim_start_matches("RRULE:").split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = match value.trim().to_ascii_uppercase().as_str() {
+                        "DAILY" => Some(Frequency::Daily),
+                        "WEEKLY" => Some(Frequency::Weekly),
+                        "MONTHLY" => Some(Frequency::Monthly),
+                        _ => None,
+                    }
+                }
+                "INTERVAL" => recurrence.interval = value.trim().parse().unwrap_or(1).max(1),
+                "BYDAY" => {
+                    recurrence.weekdays = WEEKDAYS
+                        .iter()
+                        .copied()
+                        .filter(|day| {
+                            value
+                                .split(',')
+                                .any(|code| code.trim().eq_ignore_ascii_case(weekday_code(*day)))
+                        })
+                        .collect()
+                }
+                "BYMONTHDAY" => {
+                    recurrence.month_day = value.trim().parse().ok().map(|day: u32| day.clamp(1, 31))
+                }
+                "X-FROM" => recurrence.from_completion = value.trim().eq_ignore_ascii_case("COMPLETION"),
+                _ => {}
+            }
+        }
+        recurrence.frequency = frequency?;
+        Some(recurrence)
+    }
+
+    pub fn to_rule(&self) -> String {
+        let mut parts = vec![
+            format!("FREQ={}", self.frequency.as_str()),
+            format!("INTERVAL={}", self.interval.max(1)),
+        ];
+        if self.frequency == Frequency::Weekly && !self.weekdays.is_empty() {
+            let days: Vec<&str> = self.weekdays.iter().map(|day| weekday_code(*day)).collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if let (Frequency::Monthly, Some(day)) = (self.frequency, self.month_day) {
+            parts.push(format!("BYMONTHDAY={}", day));
+        }
+        if self.from_completion {
+            parts.push("X-FROM=COMPLETION".to_string());
+        }
+        parts.join(";")
+    }
+
+    /// Short description for badges, e.g. "Every 2 weeks on Mon, Thu".
+    pub fn describe(&self) -> String {
+        let unit = match self.frequency {
+            Frequency::Daily => "day",
+            Frequency::Weekly => "week",
+            Frequency::Monthly => "month",
+        };
+        let every = match self.interval {
+            1 if self.frequency == Frequency::Daily => "Daily".to_string(),
+            1 if self.frequency == Frequency::Weekly => "Weekly".to_string(),
+            1 => "Monthly".to_string(),
+            n => format!("Every {} {}s", n, unit),
+        };
+        if self.from_completion {
+            return format!("{} after completion", every);
+        }
+        match self.frequency {
+            Frequency::Weekly if self.weekdays == WEEKDAYS[..5] && self.interval == 1 => {
+                "Every weekday".to_string()
+            }
+            Frequency::Weekly if !self.weekdays.is_empty() => {
+                let days: Vec<String> = self.weekdays.iter().map(|day| day.to_string()).collect();
+                format!("{} on {}", every, days.join(", "))
+            }
+            Frequency::Monthly if self.month_day.is_some() => {
+                format!("{} on day {}", every, self.month_day.unwrap_or(1))
+            }
+            _ => every,
+        }
+    }
+
+    /// Due date of the occurrence following one due on `due` and completed on
+    /// `completed_on`.
+    ///
+    /// Fixed schedules skip occurrences that already passed, so finishing a
+    /// weekly report late does not make the next one overdue right away.
+    pub fn next_due(&self, due: Option<NaiveDate>, completed_on: NaiveDate) -> NaiveDate {
+        let interval = self.interval.max(1);
+        if self.from_completion {
+            return self.advance(completed_on, completed_on, interval);
+        }
+        let anchor = due.unwrap_or(completed_on);
+        let mut next = self.advance(anchor, anchor, interval);
+        for _ in 0..MAX_STEPS {
+            if next > completed_on {
+                break;
+            }
+            next = self.advance(anchor, next, interval);
+        }
+        next
+    }
+
+    /// First occurrence after `after` of the schedule anchored at `anchor`.
+    fn advance(&self, anchor: NaiveDate, after: NaiveDate, interval: u32) -> NaiveDate {
+        match self.frequency {
+            Frequency::Daily => after + Days::new(interval as u64),
+            Frequency::Weekly => {
+                let days = if self.weekdays.is_empty() {
+                    vec![anchor.weekday()]
+                } else {
+                    self.weekdays.clone()
+                };
+                let first_week = week_start(anchor);
+                after
+                    .iter_days()
+                    .skip(1)
+                    .take(7 * (interval as usize + 1))
+                    .find(|day| {
+                        let week = (week_start(*day) - first_week).num_days() / 7;
+                        days.contains(&day.weekday()) && week % interval as i64 == 0
+                    })
+                    .unwrap_or(after + Days::new(7 * interval as u64))
+            }
+            Frequency::Monthly => {
+                let day = self.month_day.unwrap_or(anchor.day());
+                let mut month = month_start(anchor);
+                for _ in 0..MAX_STEPS {
+                    let candidate = month
+                        .with_day(day.min(last_day_of_month(month)))
+                        .unwrap_or(month);
+                    if candidate > after {
+                        return candidate;
+                    }
+                    month = add_months(month, interval as i32);
+                }
+                add_months(after, interval as i32)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn rules_round_trip() {
+        let rules = [
+            Recurrence::daily(1),
+            Recurrence::weekdays(),
+            Recurrence::weekly(2, vec![Weekday::Mon, Weekday::Thu]),
+            Recurrence::weekly(1, Vec::new()),
+            Recurrence::monthly(3, 31),
+            Recurrence::after_completion(4),
+        ];
+        for rule in rules {
+            assert_eq!(Recurrence::parse(&rule.to_rule()), Some(rule));
+        }
+        assert_eq!(
+            Recurrence::weekly(2, vec![Weekday::Mon, Weekday::Thu]).to_rule(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH"
+        );
+    }
+
+    #[test]
+    fn parse_is_lenient() {
+        assert_eq!(
+            Recurrence::parse("RRULE:freq=weekly;byday=th, mo;interval=0;COUNT=5"),
+            Some(Recurrence::weekly(1, vec![Weekday::Mon, Weekday::Thu]))
+        );
+        assert_eq!(
+            Recurrence::parse("FREQ=MONTHLY;BYMONTHDAY=40").and_then(|rule| rule.month_day),
+            Some(31)
+        );
+        assert_eq!(Recurrence::parse("INTERVAL=2"), None);
+        assert_eq!(Recurrence::parse("FREQ=YEARLY"), None);
+    }
+
+    #[test]
+    fn weekly_with_an_interval_skips_the_off_weeks() {
+        let rule = Recurrence::weekly(2, vec![Weekday::Mon, Weekday::Thu]);
+        let monday = date(2024, 5, 6);
+        assert_eq!(rule.next_due(Some(monday), monday), date(2024, 5, 9));
+        // the week of the 13th is off; weeks count from the one holding the due date
+        assert_eq!(rule.advance(monday, date(2024, 5, 9), 2), date(2024, 5, 20));
+        assert_eq!(rule.advance(monday, date(2024, 5, 20), 2), date(2024, 5, 23));
+    }
+
+    #[test]
+    fn monthly_clamps_to_the_last_day() {
+        let rule = Recurrence::monthly(1, 31);
+        let january = date(2024, 1, 31);
+        assert_eq!(rule.next_due(Some(january), january), date(2024, 2, 29));
+        assert_eq!(rule.next_due(Some(date(2024, 2, 29)), date(2024, 2, 29)), date(2024, 3, 31));
+        assert_eq!(rule.advance(date(2023, 1, 31), date(2023, 1, 31), 3), date(2023, 4, 30));
+        // without a month day the due date's day is kept
+        let plain = Recurrence::parse("FREQ=MONTHLY;INTERVAL=1").unwrap();
+        assert_eq!(plain.next_due(Some(date(2024, 5, 15)), date(2024, 5, 15)), date(2024, 6, 15));
+    }
+
+    #[test]
+    fn late_completions_skip_missed_occurrences() {
+        // weekly on the due date's weekday, a Friday, finished on Wednesday twelve days later
+        let weekly = Recurrence::weekly(1, Vec::new());
+        assert_eq!(weekly.next_due(Some(date(2024, 5, 3)), date(2024, 5, 15)), date(2024, 5, 17));
+
+        let daily = Recurrence::daily(2);
+        assert_eq!(daily.next_due(Some(date(2024, 5, 1)), date(2024, 5, 6)), date(2024, 5, 7));
+        // finished early, the next one still follows the due date
+        assert_eq!(daily.next_due(Some(date(2024, 5, 10)), date(2024, 5, 6)), date(2024, 5, 12));
+
+        // counted from completion, the due date does not matter
+        let after = Recurrence::after_completion(3);
+        assert_eq!(after.next_due(Some(date(2024, 5, 1)), date(2024, 5, 10)), date(2024, 5, 13));
+        assert_eq!(after.next_due(None, date(2024, 5, 10)), date(2024, 5, 13));
+    }
+}
@@ -0,0 +1,31 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+}
+
+pub fn load_item(key: &str) -> Option<String> {
+    storage().and_then(|storage| storage.get_item(key).ok().flatten())
+}
+
+/// Stores `value` under `key`, or removes the key when `value` is `None`.
+pub fn store_item(key: &str, value: Option<&str>) {
+    if let Some(storage) = storage() {
+        let _ = match value {
+            Some(value) => storage.set_item(key, value),
+            None => storage.remove_item(key),
+        };
+    }
+}
+
+/// Reads a JSON value; missing or unreadable entries yield `None`.
+pub fn load_json<T: DeserializeOwned>(key: &str) -> Option<T> {
+    load_item(key).and_then(|value| serde_json::from_str(&value).ok())
+}
+
+pub fn store_json<T: Serialize>(key: &str, value: &T) {
+    if let Ok(value) = serde_json::to_string(value) {
+        store_item(key, Some(&value));
+    }
+}
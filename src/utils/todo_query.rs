@@ -0,0 +1,197 @@
+use crate::api::models::{Priority, TodoResponse};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Pending,
+    Completed,
+}
+
+impl StatusFilter {
+    pub const ALL: [StatusFilter; 3] = [
+        StatusFilter::All,
+        StatusFilter::Pending,
+        StatusFilter::Completed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::All => "All",
+            StatusFilter::Pending => "Pending",
+            StatusFilter::Completed => "Completed",
+        }
+    }
+
+    fn matches(&self, todo: &TodoResponse) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Pending => !todo.completed,
+            StatusFilter::Completed => todo.completed,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// Drag-and-drop order (`position`).
+    #[default]
+    Manual,
+    Created,
+    Updated,
+    Due,
+    Priority,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 5] = [
+        SortKey::Manual,
+        SortKey::Created,
+        SortKey::Updated,
+        SortKey::Due,
+        SortKey::Priority,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortKey::Manual => "manual",
+            SortKey::Created => "created",
+            SortKey::Updated => "updated",
+            SortKey::Due => "due",
+            SortKey::Priority => "priority",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Manual => "Manual",
+            SortKey::Created => "Created",
+            SortKey::Updated => "Updated",
+            SortKey::Due => "Due date",
+            SortKey::Priority => "Priority",
+        }
+    }
+
+    pub fn from_str(key: &str) -> Self {
+        match key {
+            "created" => SortKey::Created,
+            "updated" => SortKey::Updated,
+            "due" => SortKey::Due,
+            "priority" => SortKey::Priority,
+            _ => SortKey::Manual,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDir {
+    #[default]
+    Asc,
+    Desc,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// View of the todo list encoded in the `/todo` query string, e.g.
+/// `?status=pending&q=report&tag=work&priority=high&sort=due&dir=desc`.
+///
+/// Default values are left out of the URL.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct TodoQuery {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub status: StatusFilter,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub q: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sort: SortKey,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub dir: SortDir,
+}
+
+impl TodoQuery {
+    pub fn is_filtered(&self) -> bool {
+        self.status != StatusFilter::All
+            || !self.q.trim().is_empty()
+            || self.tag.is_some()
+            || self.priority.is_some()
+    }
+
+    pub fn matches(&self, todo: &TodoResponse) -> bool {
+        let needle = self.q.trim().to_lowercase();
+        self.status.matches(todo)
+            && (needle.is_empty()
+                || todo.title.to_lowercase().contains(&needle)
+                || todo
+                    .description
+                    .as_ref()
+                    .is_some_and(|d| d.to_lowercase().contains(&needle)))
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| todo.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.priority.is_none_or(|p| todo.priority == p)
+    }
+
+    /// True when the list shows every todo in manual order, so moving one is meaningful.
+    pub fn allows_reorder(&self) -> bool {
+        self.sort == SortKey::Manual && self.dir == SortDir::Asc && !self.is_filtered()
+    }
+
+    /// Filters and sorts `todos` according to the query.
+    pub fn apply(&self, todos: &[TodoResponse]) -> Vec<TodoResponse> {
+        let mut visible: Vec<TodoResponse> =
+            todos.iter().filter(|todo| self.matches(todo)).cloned().collect();
+
+        visible.sort_by(|a, b| {
+            let ordering = match self.sort {
+                // unpositioned todos go last, oldest first
+                SortKey::Manual => match (a.position, b.position) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => a.created_at.cmp(&b.created_at),
+                },
+                SortKey::Created => a.created_at.cmp(&b.created_at),
+                SortKey::Updated => a.updated_at.cmp(&b.updated_at),
+                SortKey::Priority => a.priority.cmp(&b.priority),
+                // todos without a due date always go last
+                SortKey::Due => {
+                    return match (a.due(), b.due()) {
+                        (Some(x), Some(y)) if self.dir == SortDir::Desc => y.cmp(&x),
+                        (Some(x), Some(y)) => x.cmp(&y),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    };
+                }
+            };
+            match self.dir {
+                SortDir::Asc => ordering,
+                SortDir::Desc => ordering.reverse(),
+            }
+        });
+        visible
+    }
+}
+
+/// Every distinct tag used by `todos`, sorted case-insensitively.
+pub fn collect_tags(todos: &[TodoResponse]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in todos.iter().flat_map(|todo| todo.tags.iter()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags
+}
@@ -0,0 +1,704 @@
+use std::fmt;
+
+use chrono::NaiveTime;
+
+use crate::api::models::{format_due, parse_due_date, ChecklistItem, Priority, Todo, TodoResponse};
+use crate::types::Status;
+use crate::utils::ical::{export_ics, import_ics};
+
+/// File formats todos can be exported to and imported from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransferFormat {
+    /// The API's own todo objects, as an array.
+    Json,
+    /// One row per todo under a header row; see [`CSV_COLUMNS`].
+    Csv,
+    /// A `- [ ]`/`- [x]` checklist with Obsidian Tasks style markers
+    /// (`📅 2024-05-03`, `⏫`, `🔁 FREQ=...`) and `#tags`; a due time follows
+    /// the date as `⏰ 17:00`.
+    Markdown,
+    /// An iCalendar file of VTODO entries, see [`crate::utils::ical`].
+    Ical,
+}
+
+/// Columns written by the CSV export. The import only needs `title` and
+/// ignores columns it does not know.
+pub const CSV_COLUMNS: [&str; 13] = [
+    "id",
+    "title",
+    "description",
+    "completed",
+    "status",
+    "priority",
+    "due_date",
+    "tags",
+    "list_id",
+    "recurrence",
+    "checklist",
+    "created_at",
+    "updated_at",
+];
+
+impl TransferFormat {
+    pub const ALL: [TransferFormat; 4] = [
+        TransferFormat::Json,
+        TransferFormat::Csv,
+        TransferFormat::Markdown,
+        TransferFormat::Ical,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransferFormat::Json => "JSON",
+            TransferFormat::Csv => "CSV",
+            TransferFormat::Markdown => "Markdown",
+            TransferFormat::Ical => "iCalendar",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TransferFormat::Json => "json",
+            TransferFormat::Csv => "csv",
+            TransferFormat::Markdown => "md",
+            TransferFormat::Ical => "ics",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            TransferFormat::Json => "application/json",
+            TransferFormat::Csv => "text/csv",
+            TransferFormat::Markdown => "text/markdown",
+            TransferFormat::Ical => "text/calendar",
+        }
+    }
+
+    /// Format of a file named `name`, from its extension.
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(TransferFormat::Json),
+            "csv" => Some(TransferFormat::Csv),
+            "md" | "markdown" | "txt" => Some(TransferFormat::Markdown),
+            "ics" | "ical" | "ifb" => Some(TransferFormat::Ical),
+            _ => None,
+        }
+    }
+
+    /// Format of a file, from its name or else from what `text` looks like.
+    pub fn detect(name: &str, text: &str) -> Self {
+        if let Some(format) = Self::from_file_name(name) {
+            return format;
+        }
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with("BEGIN:VCALENDAR") {
+            TransferFormat::Ical
+        } else if text.starts_with('[') || text.starts_with('{') {
+            TransferFormat::Json
+        } else if text.lines().any(|line| parse_task_line(line).is_some()) {
+            TransferFormat::Markdown
+        } else {
+            TransferFormat::Csv
+        }
+    }
+}
+
+/// Why a file could not be imported.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImportError {
+    /// JSON that is not a todo or an array of todos.
+    Json(String),
+    /// CSV whose header has no `title` column.
+    MissingTitle,
+    /// Nothing in the file looked like a todo.
+    Empty,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Json(reason) => write!(f, "The file is not valid todo JSON: {}", reason),
+            ImportError::MissingTitle => write!(f, "The CSV file has no \"title\" column."),
+            ImportError::Empty => write!(f, "No todos were found in the file."),
+        }
+    }
+}
+
+/// `todos` written out in `format`.
+pub fn export_todos(todos: &[TodoResponse], format: TransferFormat) -> String {
+    match format {
+        TransferFormat::Json => serde_json::to_string_pretty(todos).unwrap_or_else(|_| "[]".to_string()),
+        TransferFormat::Csv => export_csv(todos),
+        TransferFormat::Markdown => export_markdown(todos),
+        TransferFormat::Ical => export_ics(todos),
+    }
+}
+
+/// New todos read from `text` in `format`.
+///
+/// Ids, list ids and timestamps are left out: imported todos are created
+/// afresh, in the list the import runs from.
+pub fn import_todos(text: &str, format: TransferFormat) -> Result<Vec<Todo>, ImportError> {
+    let text = text.trim_start_matches('\u{feff}');
+    let todos: Vec<Todo> = match format {
+        TransferFormat::Json => import_json(text)?,
+        TransferFormat::Csv => import_csv(text)?,
+        TransferFormat::Markdown => import_markdown(text),
+        TransferFormat::Ical => import_ics(text)?,
+    }
+    .into_iter()
+    .filter(|todo| !todo.title.is_empty())
+    .map(|todo| {
+        // keep `completed` and `status` in agreement
+        let completed = todo.completed || todo.status == Some(Status::DONE);
+        let status = todo.status.map(|status| if completed { Status::DONE } else { status });
+        Todo {
+            title: todo.title.trim().to_string(),
+            completed,
+            status,
+            list_id: None,
+            ..todo
+        }
+    })
+    .collect();
+
+    if todos.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    Ok(todos)
+}
+
+fn import_json(text: &str) -> Result<Vec<Todo>, ImportError> {
+    match serde_json::from_str::<Vec<Todo>>(text) {
+        Ok(todos) => Ok(todos),
+        // a single todo is fine too
+        Err(err) => serde_json::from_str::<Todo>(text)
+            .map(|todo| vec![todo])
+            .map_err(|_| ImportError::Json(err.to_string())),
+    }
+}
+
+fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "y" | "1" | "x" | "done"
+    )
+}
+
+fn parse_status(value: &str) -> Option<Status> {
+    let value = value.trim().to_ascii_uppercase().replace([' ', '-'], "_");
+    Status::ALL.into_iter().find(|status| status.as_str() == value)
+}
+
+/// Adds `tag` without its `#` unless it is empty or already there in another case.
+pub fn add_tag(tags: &mut Vec<String>, tag: &str) {
+    let tag = tag.trim().trim_start_matches('#').trim();
+    if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+        tags.push(tag.to_string());
+    }
+}
+
+fn parse_tags(value: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in value.split([',', ';']) {
+        add_tag(&mut tags, tag);
+    }
+    tags
+}
+
+/// Numbered checklist items from `(title, done)` pairs, skipping blank titles.
+pub fn checklist_from(items: impl IntoIterator<Item = (String, bool)>) -> Vec<ChecklistItem> {
+    items
+        .into_iter()
+        .filter(|(title, _)| !title.trim().is_empty())
+        .enumerate()
+        .map(|(index, (title, done))| ChecklistItem {
+            id: index as u32 + 1,
+            title: title.trim().to_string(),
+            done,
+        })
+        .collect()
+}
+
+/// Leading characters that make spreadsheets read a cell as a formula.
+const FORMULA_STARTS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Free text with a `'` in front when it would otherwise run as a formula in
+/// Excel or Sheets; [`import_csv`] takes it off again.
+fn csv_text(value: &str) -> String {
+    if value.starts_with(FORMULA_STARTS) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// A cell without the `'` that [`csv_text`] put in front of it.
+fn strip_formula_guard(value: &str) -> &str {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_STARTS) => rest,
+        _ => value,
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn export_csv(todos: &[TodoResponse]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push_str("\r\n");
+    for todo in todos {
+        // one "[x] item" line per checklist item
+        let checklist: Vec<String> = todo
+            .checklist
+            .iter()
+            .map(|item| format!("[{}] {}", if item.done { "x" } else { " " }, item.title))
+            .collect();
+        let row = [
+            todo.id.to_string(),
+            csv_text(&todo.title),
+            csv_text(todo.description.as_deref().unwrap_or_default()),
+            todo.completed.to_string(),
+            todo.status().as_str().to_string(),
+            todo.priority.as_str().to_string(),
+            todo.due_date.clone().unwrap_or_default(),
+            csv_text(&todo.tags.join(", ")),
+            todo.list_id.map(|id| id.to_string()).unwrap_or_default(),
+            todo.recurrence.clone().unwrap_or_default(),
+            csv_text(&checklist.join("\n")),
+            todo.created_at.clone(),
+            todo.updated_at.clone(),
+        ];
+        let fields: Vec<String> = row.iter().map(|value| csv_field(value)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Rows of a CSV document; quoted fields may hold commas, quotes and newlines.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    // blank lines
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+fn import_csv(text: &str) -> Result<Vec<Todo>, ImportError> {
+    let mut rows = parse_csv(text).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or(ImportError::Empty)?
+        .iter()
+        .map(|name| name.trim().to_ascii_lowercase().replace(' ', "_"))
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|name| names.contains(&name.as_str()));
+    let title = column(&["title", "name", "task"]).ok_or(ImportError::MissingTitle)?;
+    let description = column(&["description", "notes"]);
+    let completed = column(&["completed", "done"]);
+    let status = column(&["status"]);
+    let priority = column(&["priority"]);
+    let due_date = column(&["due_date", "due"]);
+    let tags = column(&["tags", "labels"]);
+    let recurrence = column(&["recurrence"]);
+    let checklist = column(&["checklist"]);
+
+    Ok(rows
+        .map(|row| {
+            let cell = |index: Option<usize>| {
+                index
+                    .and_then(|index| row.get(index))
+                    .map(|value| strip_formula_guard(value.trim()))
+                    .unwrap_or("")
+            };
+            let optional = |index: Option<usize>| Some(cell(index).to_string()).filter(|value| !value.is_empty());
+            let items = cell(checklist)
+                .lines()
+                .map(|line| match parse_checkbox(line.trim()) {
+                    Some((done, title)) => (title.to_string(), done),
+                    None => (line.trim().to_string(), false),
+                });
+            Todo {
+                title: cell(Some(title)).to_string(),
+                completed: parse_flag(cell(completed)),
+                description: optional(description),
+                due_date: optional(due_date),
+                priority: optional(priority)
+                    .map(|value| Priority::from_str(&value.to_ascii_lowercase()))
+                    .unwrap_or_default(),
+                tags: parse_tags(cell(tags)),
+                checklist: checklist_from(items),
+                list_id: None,
+                status: parse_status(cell(status)),
+                recurrence: optional(recurrence),
+            }
+        })
+        .collect())
+}
+
+fn priority_marker(priority: Priority) -> Option<&'static str> {
+    match priority {
+        Priority::Urgent => Some("🔺"),
+        Priority::High => Some("⏫"),
+        // the default, so left out
+        Priority::Medium => None,
+        Priority::Low => Some("🔽"),
+    }
+}
+
+fn export_markdown(todos: &[TodoResponse]) -> String {
+    let mut out = String::new();
+    for todo in todos {
+        let mark = match todo.status() {
+            Status::DONE => "x",
+            Status::IN_PROGRESS => "/",
+            Status::PENDING => " ",
+        };
+        let mut line = format!("- [{}] {}", mark, todo.title.replace('\n', " "));
+        if let Some(marker) = priority_marker(todo.priority) {
+            line.push_str(&format!(" {}", marker));
+        }
+        if let Some(rule) = &todo.recurrence {
+            line.push_str(&format!(" 🔁 {}", rule));
+        }
+        if let Some(due) = todo.due() {
+            line.push_str(&format!(" 📅 {}", due.format("%Y-%m-%d")));
+        }
+        if let Some(time) = todo.due_time() {
+            line.push_str(&format!(" ⏰ {}", time.format("%H:%M")));
+        }
+        for tag in &todo.tags {
+            line.push_str(&format!(" #{}", tag.replace(' ', "-")));
+        }
+        out.push_str(&line);
+        out.push('\n');
+
+        if let Some(description) = &todo.description {
+            for text in description.lines() {
+                out.push_str(&format!("  {}\n", text));
+            }
+        }
+        for item in &todo.checklist {
+            out.push_str(&format!("  - [{}] {}\n", if item.done { "x" } else { " " }, item.title));
+        }
+    }
+    out
+}
+
+/// `[x] rest` or `[ ] rest` split into the box and the rest.
+fn parse_checkbox(text: &str) -> Option<(bool, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let (mark, rest) = rest.split_once(']')?;
+    let done = match mark {
+        " " | "" => false,
+        "x" | "X" => true,
+        _ => return None,
+    };
+    Some((done, rest.trim()))
+}
+
+/// A `- [ ] text` line: its indent, mark and text. `[/]` is in progress.
+fn parse_task_line(line: &str) -> Option<(usize, char, &str)> {
+    let indent = line.len() - line.trim_start().len() + line.chars().take_while(|c| *c == '\t').count();
+    let text = line.trim_start();
+    let rest = text
+        .strip_prefix("- ")
+        .or_else(|| text.strip_prefix("* "))
+        .or_else(|| text.strip_prefix("+ "))?
+        .trim_start();
+    let rest = rest.strip_prefix('[')?;
+    let (mark, text) = rest.split_once(']')?;
+    let mark = match mark {
+        " " | "" => ' ',
+        "x" | "X" => 'x',
+        "/" => '/',
+        _ => return None,
+    };
+    Some((indent, mark, text.trim()))
+}
+
+/// Gives the last todo the checklist and description lines read after it.
+fn finish_task(todos: &mut [Todo], checklist: &mut Vec<(String, bool)>, description: &mut Vec<String>) {
+    if let Some(todo) = todos.last_mut() {
+        if !checklist.is_empty() {
+            todo.checklist = checklist_from(std::mem::take(checklist));
+        }
+        if !description.is_empty() {
+            todo.description = Some(std::mem::take(description).join("\n"));
+        }
+    }
+}
+
+fn import_markdown(text: &str) -> Vec<Todo> {
+    let mut todos: Vec<Todo> = Vec::new();
+    let mut checklist: Vec<(String, bool)> = Vec::new();
+    let mut description: Vec<String> = Vec::new();
+    // whether indented lines still belong to the last todo
+    let mut open = false;
+
+    for line in text.lines() {
+        let indented = line.starts_with("  ") || line.starts_with('\t');
+        match parse_task_line(line) {
+            Some((indent, mark, text)) if indent < 2 => {
+                finish_task(&mut todos, &mut checklist, &mut description);
+                todos.push(parse_task_text(text, mark));
+                open = true;
+            }
+            Some((_, mark, text)) if open => checklist.push((text.to_string(), mark == 'x')),
+            _ if open && indented && !line.trim().is_empty() => description.push(line.trim().to_string()),
+            // blank lines keep the todo open; headings and paragraphs end it
+            _ if line.trim().is_empty() => {}
+            _ => {
+                finish_task(&mut todos, &mut checklist, &mut description);
+                open = false;
+            }
+        }
+    }
+    finish_task(&mut todos, &mut checklist, &mut description);
+    todos
+}
+
+/// Title and markers of a task line.
+fn parse_task_text(text: &str, mark: char) -> Todo {
+    let mut todo = Todo {
+        completed: mark == 'x',
+        status: match mark {
+            'x' => Some(Status::DONE),
+            '/' => Some(Status::IN_PROGRESS),
+            _ => None,
+        },
+        ..Todo::default()
+    };
+    let mut title: Vec<&str> = Vec::new();
+    let mut due_time = None;
+    let mut words = text.split_whitespace();
+    while let Some(word) = words.next() {
+        match word {
+            "🔺" => todo.priority = Priority::Urgent,
+            "⏫" => todo.priority = Priority::High,
+            "🔼" => todo.priority = Priority::Medium,
+            "🔽" | "⏬" => todo.priority = Priority::Low,
+            "📅" | "🗓️" => todo.due_date = words.next().filter(|due| parse_due_date(due).is_some()).map(str::to_string),
+            "⏰" => due_time = words.next().and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok()),
+            "🔁" => todo.recurrence = words.next().map(str::to_string),
+            // done and start dates are not kept
+            "✅" | "🛫" | "⏳" | "➕" => {
+                words.next();
+            }
+            tag if tag.len() > 1 && tag.starts_with('#') => {
+                let tag = &tag[1..];
+                if !todo.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    todo.tags.push(tag.to_string());
+                }
+            }
+            word => title.push(word),
+        }
+    }
+    todo.title = title.join(" ");
+    if let Some(date) = todo.due_date.as_deref().and_then(parse_due_date) {
+        todo.due_date = Some(format_due(date, due_time));
+    }
+    todo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::fixtures::{sample_todos as sample, todo};
+    use chrono::NaiveDate;
+
+    /// What an import of `sample()` should give back.
+    fn expected() -> Vec<Todo> {
+        sample()
+            .iter()
+            .map(|todo| Todo {
+                title: todo.title.clone(),
+                completed: todo.completed,
+                description: todo.description.clone(),
+                due_date: todo.due_date.clone(),
+                priority: todo.priority,
+                tags: todo.tags.clone(),
+                checklist: todo.checklist.clone(),
+                list_id: None,
+                status: Some(todo.status()),
+                recurrence: todo.recurrence.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let text = export_todos(&sample(), TransferFormat::Json);
+        let mut imported = import_todos(&text, TransferFormat::Json).unwrap();
+        // JSON keeps a missing status missing
+        imported[0].status = Some(Status::PENDING);
+        assert_eq!(imported, expected());
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let text = export_todos(&sample(), TransferFormat::Csv);
+        assert!(text.starts_with("id,title,description,completed,status,"));
+        assert!(text.contains("\"Bring the \"\"big\"\" box,\nand the tape\""));
+        assert_eq!(import_todos(&text, TransferFormat::Csv).unwrap(), expected());
+    }
+
+    #[test]
+    fn csv_cells_never_start_a_formula() {
+        let todos = vec![TodoResponse {
+            description: Some("-1 for the idea".to_string()),
+            tags: vec!["@home".to_string()],
+            ..todo(1, "=HYPERLINK(\"http://example.com\", \"Click\")")
+        }];
+        let text = export_todos(&todos, TransferFormat::Csv);
+        let row = text.lines().nth(1).unwrap();
+        assert!(row.starts_with("1,\"'=HYPERLINK(\"\"http://example.com\"\", \"\"Click\"\")\","));
+        assert!(row.contains(",'-1 for the idea,"));
+        assert!(row.contains(",'@home,"));
+
+        let imported = import_todos(&text, TransferFormat::Csv).unwrap();
+        assert_eq!(imported[0].title, todos[0].title);
+        assert_eq!(imported[0].description, todos[0].description);
+        assert_eq!(imported[0].tags, vec!["@home".to_string()]);
+        // a quote the user typed stays
+        assert_eq!(import_todos("title\n'quoted\n", TransferFormat::Csv).unwrap()[0].title, "'quoted");
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        let text = export_todos(&sample(), TransferFormat::Markdown);
+        assert!(text.starts_with(
+            "- [ ] Pack, then ship ⏫ 🔁 FREQ=WEEKLY;INTERVAL=1;BYDAY=FR 📅 2024-05-03 #home #errands\n"
+        ));
+        assert!(text.contains("- [x] Pay rent 🔽\n"));
+        assert!(text.contains("- [/] Write report 🔺\n"));
+
+        let mut wanted = expected();
+        // the pending status is implied by the empty box
+        wanted[0].status = None;
+        assert_eq!(import_todos(&text, TransferFormat::Markdown).unwrap(), wanted);
+    }
+
+    #[test]
+    fn markdown_keeps_the_due_time() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+        let due = format_due(date, NaiveTime::from_hms_opt(17, 0, 0));
+        let todos = vec![TodoResponse {
+            due_date: Some(due.clone()),
+            ..todo(1, "Call mom")
+        }];
+        let text = export_todos(&todos, TransferFormat::Markdown);
+        assert_eq!(text, "- [ ] Call mom 📅 2024-05-03 ⏰ 17:00\n");
+        let imported = import_todos(&text, TransferFormat::Markdown).unwrap();
+        assert_eq!(imported[0].due_date, Some(due));
+    }
+
+    #[test]
+    fn json_accepts_a_single_todo_and_missing_fields() {
+        let imported = import_todos(r#"{"title": "Solo", "status": "DONE"}"#, TransferFormat::Json).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].title, "Solo");
+        assert!(imported[0].completed);
+        assert_eq!(imported[0].priority, Priority::Medium);
+
+        assert!(matches!(
+            import_todos("{\"nope\": 1}", TransferFormat::Json),
+            Err(ImportError::Json(_))
+        ));
+        assert_eq!(import_todos("[]", TransferFormat::Json), Err(ImportError::Empty));
+    }
+
+    #[test]
+    fn csv_from_other_tools() {
+        let text = "\u{feff}Name,Done,Due,Labels,Whatever\r\n\
+                    Buy milk,yes,2024-05-03,\"#food; shop\",ignored\r\n\
+                    \r\n\
+                    ,no,,,\r\n\
+                    Call Bob,,,,\n";
+        let imported = import_todos(text, TransferFormat::Csv).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].title, "Buy milk");
+        assert!(imported[0].completed);
+        assert_eq!(imported[0].status, None);
+        assert_eq!(imported[0].due_date.as_deref(), Some("2024-05-03"));
+        assert_eq!(imported[0].tags, vec!["food".to_string(), "shop".to_string()]);
+        assert_eq!(imported[1].title, "Call Bob");
+        assert!(!imported[1].completed);
+
+        assert_eq!(import_todos("a,b\n1,2\n", TransferFormat::Csv), Err(ImportError::MissingTitle));
+        assert_eq!(import_todos("", TransferFormat::Csv), Err(ImportError::Empty));
+    }
+
+    #[test]
+    fn markdown_from_notes() {
+        let text = "# Groceries\n\
+                    Some intro text.\n\
+                    * [X] Eggs\n\
+                    - [ ] Milk #dairy 📅 2024-05-03 ✅ 2024-05-04\n\
+                    \tsemi-skimmed\n\
+                    \n    - [x] check fridge\n\
+                    - plain bullet\n\
+                    \x20   - [ ] not a subtask anymore\n\
+                    - [-] cancelled is not a task\n";
+        let imported = import_todos(text, TransferFormat::Markdown).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].title, "Eggs");
+        assert!(imported[0].completed);
+        assert_eq!(imported[1].title, "Milk");
+        assert_eq!(imported[1].tags, vec!["dairy".to_string()]);
+        assert_eq!(imported[1].due_date.as_deref(), Some("2024-05-03"));
+        assert_eq!(imported[1].description.as_deref(), Some("semi-skimmed"));
+        assert_eq!(imported[1].checklist.len(), 1);
+        assert!(imported[1].checklist[0].done);
+
+        assert_eq!(import_todos("just text", TransferFormat::Markdown), Err(ImportError::Empty));
+    }
+
+    #[test]
+    fn imports_drop_list_ids() {
+        let text = r#"[{"title": "Moved", "completed": false, "list_id": 4}]"#;
+        assert_eq!(import_todos(text, TransferFormat::Json).unwrap()[0].list_id, None);
+    }
+
+    #[test]
+    fn detects_formats() {
+        assert_eq!(TransferFormat::detect("todos.CSV", "[]"), TransferFormat::Csv);
+        assert_eq!(TransferFormat::detect("notes.markdown", ""), TransferFormat::Markdown);
+        assert_eq!(TransferFormat::detect("export", " [{\"title\": \"a\"}]"), TransferFormat::Json);
+        assert_eq!(TransferFormat::detect("export", "# List\n- [ ] a"), TransferFormat::Markdown);
+        assert_eq!(TransferFormat::detect("export", "title\na"), TransferFormat::Csv);
+        assert_eq!(TransferFormat::detect("tasks.ICS", ""), TransferFormat::Ical);
+        assert_eq!(TransferFormat::detect("export", "BEGIN:VCALENDAR\r\n"), TransferFormat::Ical);
+    }
+}